@@ -0,0 +1,176 @@
+// bitboard.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops;
+
+use {Direction, Occupancy, Papamu, Position};
+
+pub const MAX_BITBOARD_CELLS: usize = 128;
+
+static OCCUPANCIES: [Occupancy; 3] = [Occupancy::White, Occupancy::Black, Occupancy::Empty];
+
+// Square (x, y) lives at bit `x * height + y`, so ascending bit order visits squares in the same
+// column-major order as `Papamu`'s own iteration.
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+pub struct Bitboard {
+  width: u8,
+  height: u8,
+  white: u128,
+  black: u128,
+  board_mask: u128,
+  bottom_mask: u128,
+  top_mask: u128,
+}
+
+impl Bitboard {
+  pub fn new(papamu: &Papamu) -> Option<Bitboard> {
+    let (width, height) = (papamu.width(), papamu.height());
+    if usize::from(width) * usize::from(height) > MAX_BITBOARD_CELLS {
+      return None;
+    }
+    let mut bitboard = Bitboard {
+      width: width,
+      height: height,
+      white: 0,
+      black: 0,
+      board_mask: 0,
+      bottom_mask: 0,
+      top_mask: 0,
+    };
+    for x in 0..width {
+      for y in 0..height {
+        let pos = Position::new(x, y).unwrap_or_else(|| unreachable!());
+        let bit = bitboard.bit(pos);
+        bitboard.board_mask |= bit;
+        if y == 0 {
+          bitboard.bottom_mask |= bit;
+        }
+        if y == height - 1 {
+          bitboard.top_mask |= bit;
+        }
+        match papamu[pos] {
+          Occupancy::White => bitboard.white |= bit,
+          Occupancy::Black => bitboard.black |= bit,
+          Occupancy::Empty => {},
+        }
+      }
+    }
+    Some(bitboard)
+  }
+
+  pub fn width(&self) -> u8 { self.width }
+
+  pub fn height(&self) -> u8 { self.height }
+
+  pub fn white(&self) -> u128 { self.white }
+
+  pub fn black(&self) -> u128 { self.black }
+
+  pub fn empty(&self) -> u128 { self.board_mask & !(self.white | self.black) }
+
+  pub fn pieces(&self, player: Occupancy) -> u128 {
+    match player {
+      Occupancy::White => self.white,
+      Occupancy::Black => self.black,
+      Occupancy::Empty => self.empty(),
+    }
+  }
+
+  pub fn contains(&self, pos: Position) -> bool { pos.x() < self.width && pos.y() < self.height }
+
+  pub fn bit(&self, pos: Position) -> u128 {
+    if self.contains(pos) {
+      1 << (u32::from(pos.x()) * u32::from(self.height) + u32::from(pos.y()))
+    } else {
+      0
+    }
+  }
+
+  pub fn position(&self, index: u32) -> Option<Position> {
+    if index < u32::from(self.width) * u32::from(self.height) {
+      Position::new((index / u32::from(self.height)) as u8,
+                    (index % u32::from(self.height)) as u8)
+    } else {
+      None
+    }
+  }
+
+  pub fn shift(&self, mask: u128, direction: Direction) -> u128 {
+    let height = u32::from(self.height);
+    match direction {
+      Direction::Up => (mask & !self.top_mask) << 1,
+      Direction::Down => (mask & !self.bottom_mask) >> 1,
+      Direction::Left => mask >> height,
+      Direction::Right => (mask << height) & self.board_mask,
+    }
+  }
+
+  pub fn jumpers(&self, player: Occupancy, direction: Direction) -> u128 {
+    let opponents = self.board_mask & !self.pieces(player) & !self.empty();
+    let back = direction.reverse();
+    self.pieces(player) & self.shift(opponents & self.shift(self.empty(), back), back)
+  }
+
+  pub fn movable(&self, player: Occupancy) -> u128 {
+    self.jumpers(player, Direction::Up) | self.jumpers(player, Direction::Down) |
+    self.jumpers(player, Direction::Left) | self.jumpers(player, Direction::Right)
+  }
+
+  pub fn can_jump(&self, player: Occupancy, source: Position, direction: Direction, jumps: u8) -> bool {
+    let mut current = self.bit(source);
+    if current & self.pieces(player) == 0 || player.is_empty() || jumps == 0 {
+      return false;
+    }
+    let opponents = self.board_mask & !self.pieces(player) & !self.empty();
+    for _ in 0..jumps {
+      let mid = self.shift(current, direction);
+      current = self.shift(mid, direction);
+      if mid & opponents == 0 || current & self.empty() == 0 {
+        return false;
+      }
+    }
+    true
+  }
+
+  pub fn to_papamu(&self) -> Papamu {
+    let mut papamu = Papamu::new(self.width, self.height).unwrap_or_else(|| unreachable!());
+    for x in 0..self.width {
+      for y in 0..self.height {
+        let pos = Position::new(x, y).unwrap_or_else(|| unreachable!());
+        papamu[pos] = self[pos];
+      }
+    }
+    papamu
+  }
+}
+
+impl ops::Index<Position> for Bitboard {
+  type Output = Occupancy;
+
+  fn index(&self, ix: Position) -> &Occupancy {
+    let bit = self.bit(ix);
+    if self.white & bit != 0 {
+      &OCCUPANCIES[0]
+    } else if self.black & bit != 0 {
+      &OCCUPANCIES[1]
+    } else {
+      &OCCUPANCIES[2]
+    }
+  }
+}
+
+impl From<Bitboard> for Papamu {
+  fn from(bitboard: Bitboard) -> Papamu { bitboard.to_papamu() }
+}
@@ -0,0 +1,126 @@
+// builder.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use {Game, GameState, Occupancy, Papamu, Position, RuleSet, errors};
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub struct GameBuilder {
+  width: u8,
+  height: u8,
+  full: bool,
+  placements: Vec<(Position, Occupancy)>,
+  to_move: Occupancy,
+  plies: u32,
+  rules: RuleSet,
+}
+
+impl Default for GameBuilder {
+  fn default() -> GameBuilder { GameBuilder::new(10, 10) }
+}
+
+impl From<Game> for GameBuilder {
+  fn from(game: Game) -> GameBuilder {
+    GameBuilder::from_papamu(game.papamu())
+      .to_move(game.current_player())
+      .plies(game.plies())
+      .rules(game.rules())
+  }
+}
+
+impl GameBuilder {
+  pub fn new(width: u8, height: u8) -> GameBuilder {
+    GameBuilder {
+      width: width,
+      height: height,
+      full: false,
+      placements: Vec::new(),
+      to_move: Occupancy::Black,
+      plies: 0,
+      rules: RuleSet::default(),
+    }
+  }
+
+  pub fn from_papamu(papamu: &Papamu) -> GameBuilder {
+    let mut builder = GameBuilder::new(papamu.width(), papamu.height());
+    builder.placements = papamu.positions()
+                               .filter(|&pos| papamu[pos].is_occupied())
+                               .map(|pos| (pos, papamu[pos]))
+                               .collect();
+    builder
+  }
+
+  // Starts from a full board rather than an empty one; placements and removals made before or after this
+  // call still apply on top of it.
+  pub fn full(mut self) -> GameBuilder {
+    self.full = true;
+    self
+  }
+
+  pub fn place(mut self, pos: Position, occ: Occupancy) -> GameBuilder {
+    self.placements.push((pos, occ));
+    self
+  }
+
+  pub fn place_all<Ps: IntoIterator<Item = Position>>(self, positions: Ps, occ: Occupancy) -> GameBuilder {
+    positions.into_iter().fold(self, |builder, pos| builder.place(pos, occ))
+  }
+
+  pub fn remove(self, pos: Position) -> GameBuilder { self.place(pos, Occupancy::Empty) }
+
+  pub fn to_move(mut self, player: Occupancy) -> GameBuilder {
+    self.to_move = player;
+    self
+  }
+
+  pub fn plies(mut self, plies: u32) -> GameBuilder {
+    self.plies = plies;
+    self
+  }
+
+  pub fn rules(mut self, rules: RuleSet) -> GameBuilder {
+    self.rules = rules;
+    self
+  }
+
+  pub fn build_papamu(&self) -> errors::Result<Papamu> {
+    let mut papamu = try!(Papamu::new(self.width, self.height)
+                            .ok_or_else(|| errors::Error::BadBoardSize {
+                              width: self.width,
+                              height: self.height,
+                            }));
+    if !self.full {
+      for pos in papamu.positions() {
+        papamu[pos] = Occupancy::Empty;
+      }
+    }
+    for &(pos, occ) in &self.placements {
+      if !papamu.contains(pos) {
+        try!(Err(errors::Error::OffBoard { pos: pos, width: self.width, height: self.height }))
+      }
+      papamu[pos] = occ;
+    }
+    try!(papamu.check_parity());
+    Ok(papamu)
+  }
+
+  pub fn build(&self) -> errors::Result<Game> {
+    let papamu = try!(self.build_papamu());
+    match self.to_move {
+      Occupancy::White => Ok(Game::White(GameState::from_papamu(papamu, self.plies, self.rules))),
+      Occupancy::Black => Ok(Game::Black(GameState::from_papamu(papamu, self.plies, self.rules))),
+      Occupancy::Empty => Err(errors::Error::EmptyTurn),
+    }
+  }
+}
@@ -83,9 +83,25 @@ pub extern "C" fn konane_game_create_black() -> *mut KonaneGame {
   Box::into_raw(Box::new(::Game::new_black().into()))
 }
 
+#[no_mangle]
+pub extern "C" fn konane_game_create_white_sized(width: libc::uint8_t,
+                                                 height: libc::uint8_t)
+                                                 -> *mut KonaneGame {
+  ::Game::new_white_sized(width as u8, height as u8).map_or_else(ptr::null_mut,
+                                                                 |game| Box::into_raw(Box::new(game.into())))
+}
+
+#[no_mangle]
+pub extern "C" fn konane_game_create_black_sized(width: libc::uint8_t,
+                                                 height: libc::uint8_t)
+                                                 -> *mut KonaneGame {
+  ::Game::new_black_sized(width as u8, height as u8).map_or_else(ptr::null_mut,
+                                                                 |game| Box::into_raw(Box::new(game.into())))
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn konane_game_destroy(game_ref: *mut KonaneGame) {
-  if game_ref.is_null() {
+  if !game_ref.is_null() {
     let game_box = Box::from_raw(game_ref);
     drop(game_box);
   }
@@ -93,27 +109,117 @@ pub unsafe extern "C" fn konane_game_destroy(game_ref: *mut KonaneGame) {
 
 #[no_mangle]
 pub unsafe extern "C" fn konane_game_next_turn(game_ref: *mut KonaneGame,
-                                               source_position_ref: *const KonanePosition,
-                                               target_positions_ref: *const *const KonanePosition,
-                                               target_positions_count: libc::size_t)
+                                               move_ref: *const KonaneMove)
                                                -> bool {
   let mut result = false;
   if let Some(&initial_game) = game_ref.as_ref() {
-    if let Some(&source_position) = source_position_ref.as_ref() {
-      if !target_positions_ref.is_null() {
-        let mut game: ::Game = initial_game.into();
-        let target_positions: Box<iter::Iterator<Item = ::Position>> =
-          Box::new(slice::from_raw_parts(target_positions_ref, target_positions_count as usize)
-            .into_iter()
-            .flat_map(|&target_position_ref: &*const KonanePosition| {
-              target_position_ref.as_ref().into_iter()
-            })
-            .map(|&target_position: &KonanePosition| target_position.into()));
-        if let Ok(_) = game.next_turn(source_position.into(), target_positions) {
-          result = true;
-        }
-        ptr::write(game_ref, game.into());
+    if let Some(&mv) = move_ref.as_ref() {
+      let mut game: ::Game = initial_game.into();
+      if let Ok(_) = game.next_turn(mv.into()) {
+        result = true;
       }
+      ptr::write(game_ref, game.into());
+    }
+  }
+  result
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_game_remove_piece(game_ref: *mut KonaneGame,
+                                                  position_ref: *const KonanePosition)
+                                                  -> bool {
+  let mut result = false;
+  if let Some(&initial_game) = game_ref.as_ref() {
+    if let Some(&position) = position_ref.as_ref() {
+      let mut game: ::Game = initial_game.into();
+      if let Ok(_) = game.remove_piece(position.into()) {
+        result = true;
+      }
+      ptr::write(game_ref, game.into());
+    }
+  }
+  result
+}
+
+// The discriminants are the stable codes from `Error::code`, so they never change between releases.
+#[allow(enum_variant_names)]
+#[derive(Copy,Clone,Debug)]
+#[repr(C)]
+#[no_mangle]
+pub enum KonaneError {
+  KonaneErrorNone = 0,
+  KonaneErrorIllegalTarget = 1,
+  KonaneErrorOccupiedTarget = 2,
+  KonaneErrorEmptySource = 3,
+  KonaneErrorWrongColor = 4,
+  KonaneErrorNoTargets = 5,
+  KonaneErrorIllegalJump = 6,
+  KonaneErrorIllegalRemoval = 7,
+  KonaneErrorOpeningComplete = 8,
+  KonaneErrorOpeningIncomplete = 9,
+  KonaneErrorTurningJump = 10,
+  KonaneErrorOffBoardJump = 11,
+  KonaneErrorIllegalPass = 12,
+  KonaneErrorLongJumpPath = 13,
+  KonaneErrorGameOver = 14,
+  KonaneErrorBadNotation = 15,
+  KonaneErrorMisplacedPiece = 16,
+  KonaneErrorBadBoardSize = 17,
+  KonaneErrorOffBoard = 18,
+  KonaneErrorEmptyTurn = 19,
+  KonaneErrorBadDiagram = 20,
+  KonaneErrorBadKgn = 21,
+  KonaneErrorIo = 22,
+  KonaneErrorNoSuchPly = 23,
+  KonaneErrorBadTablebase = 24,
+  KonaneErrorNullPointer = 255,
+}
+
+impl<'a> From<&'a ::errors::Error> for KonaneError {
+  fn from(err: &'a ::errors::Error) -> KonaneError {
+    match *err {
+      ::errors::Error::IllegalTarget { .. } => KonaneError::KonaneErrorIllegalTarget,
+      ::errors::Error::OccupiedTarget { .. } => KonaneError::KonaneErrorOccupiedTarget,
+      ::errors::Error::EmptySource { .. } => KonaneError::KonaneErrorEmptySource,
+      ::errors::Error::WrongColor { .. } => KonaneError::KonaneErrorWrongColor,
+      ::errors::Error::NoTargets { .. } => KonaneError::KonaneErrorNoTargets,
+      ::errors::Error::IllegalJump { .. } => KonaneError::KonaneErrorIllegalJump,
+      ::errors::Error::IllegalRemoval { .. } => KonaneError::KonaneErrorIllegalRemoval,
+      ::errors::Error::OpeningComplete { .. } => KonaneError::KonaneErrorOpeningComplete,
+      ::errors::Error::OpeningIncomplete { .. } => KonaneError::KonaneErrorOpeningIncomplete,
+      ::errors::Error::TurningJump { .. } => KonaneError::KonaneErrorTurningJump,
+      ::errors::Error::OffBoardJump { .. } => KonaneError::KonaneErrorOffBoardJump,
+      ::errors::Error::IllegalPass { .. } => KonaneError::KonaneErrorIllegalPass,
+      ::errors::Error::LongJumpPath { .. } => KonaneError::KonaneErrorLongJumpPath,
+      ::errors::Error::GameOver { .. } => KonaneError::KonaneErrorGameOver,
+      ::errors::Error::BadNotation { .. } => KonaneError::KonaneErrorBadNotation,
+      ::errors::Error::MisplacedPiece { .. } => KonaneError::KonaneErrorMisplacedPiece,
+      ::errors::Error::BadBoardSize { .. } => KonaneError::KonaneErrorBadBoardSize,
+      ::errors::Error::OffBoard { .. } => KonaneError::KonaneErrorOffBoard,
+      ::errors::Error::EmptyTurn => KonaneError::KonaneErrorEmptyTurn,
+      ::errors::Error::BadDiagram { .. } => KonaneError::KonaneErrorBadDiagram,
+      ::errors::Error::BadKgn { .. } => KonaneError::KonaneErrorBadKgn,
+      ::errors::Error::Io { .. } => KonaneError::KonaneErrorIo,
+      ::errors::Error::NoSuchPly { .. } => KonaneError::KonaneErrorNoSuchPly,
+      ::errors::Error::BadTablebase { .. } => KonaneError::KonaneErrorBadTablebase,
+    }
+  }
+}
+
+// Like `konane_game_next_turn`, but reports why an illegal move was refused.
+#[no_mangle]
+pub unsafe extern "C" fn konane_game_play(game_ref: *mut KonaneGame,
+                                          move_ref: *const KonaneMove)
+                                          -> KonaneError {
+  let mut result = KonaneError::KonaneErrorNullPointer;
+  if let Some(&initial_game) = game_ref.as_ref() {
+    if let Some(&mv) = move_ref.as_ref() {
+      let mut game: ::Game = initial_game.into();
+      result = match game.next_turn(mv.into()) {
+        Ok(_) => KonaneError::KonaneErrorNone,
+        Err(ref err) => err.into(),
+      };
+      ptr::write(game_ref, game.into());
     }
   }
   result
@@ -129,6 +235,48 @@ pub unsafe extern "C" fn konane_game_can_move(game_ref: *const KonaneGame) -> bo
   result
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn konane_game_get_winner(game_ref: *const KonaneGame) -> KonaneOccupancy {
+  let mut result = KonaneOccupancy::KonaneOccupancyEmpty;
+  if let Some(&initial_game) = game_ref.as_ref() {
+    let game: ::Game = initial_game.into();
+    if let Some(outcome) = game.outcome() {
+      result = outcome.winner.into();
+    }
+  }
+  result
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_game_get_plies(game_ref: *const KonaneGame) -> libc::uint32_t {
+  let mut result = 0;
+  if let Some(&initial_game) = game_ref.as_ref() {
+    let game: ::Game = initial_game.into();
+    result = game.plies() as libc::uint32_t;
+  }
+  result
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_game_get_width(game_ref: *const KonaneGame) -> libc::uint8_t {
+  let mut result = 0;
+  if let Some(&initial_game) = game_ref.as_ref() {
+    let game: ::Game = initial_game.into();
+    result = game.papamu().width() as libc::uint8_t;
+  }
+  result
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_game_get_height(game_ref: *const KonaneGame) -> libc::uint8_t {
+  let mut result = 0;
+  if let Some(&initial_game) = game_ref.as_ref() {
+    let game: ::Game = initial_game.into();
+    result = game.papamu().height() as libc::uint8_t;
+  }
+  result
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn konane_game_get_occupancy(game_ref: *const KonaneGame,
                                                    position_ref: *const KonanePosition)
@@ -137,7 +285,9 @@ pub unsafe extern "C" fn konane_game_get_occupancy(game_ref: *const KonaneGame,
   if let Some(&initial_game) = game_ref.as_ref() {
     if let Some(&position) = position_ref.as_ref() {
       let game: ::Game = initial_game.into();
-      result = game[position.into()].into();
+      if game.papamu().contains(position.into()) {
+        result = game[position.into()].into();
+      }
     }
   }
   result
@@ -153,6 +303,128 @@ pub unsafe extern "C" fn konane_game_get_current_player(game_ref: *const KonaneG
   result
 }
 
+#[allow(enum_variant_names)]
+#[derive(Copy,Clone,Debug)]
+#[repr(C)]
+#[no_mangle]
+pub enum KonaneDirection {
+  KonaneDirectionUp,
+  KonaneDirectionDown,
+  KonaneDirectionLeft,
+  KonaneDirectionRight,
+}
+
+impl From<::Direction> for KonaneDirection {
+  fn from(direction: ::Direction) -> KonaneDirection {
+    match direction {
+      ::Direction::Up => KonaneDirection::KonaneDirectionUp,
+      ::Direction::Down => KonaneDirection::KonaneDirectionDown,
+      ::Direction::Left => KonaneDirection::KonaneDirectionLeft,
+      ::Direction::Right => KonaneDirection::KonaneDirectionRight,
+    }
+  }
+}
+
+impl From<KonaneDirection> for ::Direction {
+  fn from(direction: KonaneDirection) -> ::Direction {
+    match direction {
+      KonaneDirection::KonaneDirectionUp => ::Direction::Up,
+      KonaneDirection::KonaneDirectionDown => ::Direction::Down,
+      KonaneDirection::KonaneDirectionLeft => ::Direction::Left,
+      KonaneDirection::KonaneDirectionRight => ::Direction::Right,
+    }
+  }
+}
+
+#[derive(Copy,Clone,Debug)]
+#[repr(C)]
+#[no_mangle]
+pub struct KonaneMove(::Move);
+
+impl From<::Move> for KonaneMove {
+  fn from(mv: ::Move) -> KonaneMove { KonaneMove(mv) }
+}
+
+impl From<KonaneMove> for ::Move {
+  fn from(mv: KonaneMove) -> ::Move { mv.0 }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_move_create_removal(target_position_ref: *const KonanePosition)
+                                                    -> *mut KonaneMove {
+  target_position_ref.as_ref().map_or_else(ptr::null_mut, |&target_position| {
+    Box::into_raw(Box::new(::Move::remove(target_position.into()).into()))
+  })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_move_create_jump(source_position_ref: *const KonanePosition,
+                                                 direction: KonaneDirection,
+                                                 jumps: libc::uint8_t)
+                                                 -> *mut KonaneMove {
+  source_position_ref.as_ref().map_or_else(ptr::null_mut, |&source_position| {
+    Box::into_raw(Box::new(::Move::jump(source_position.into(), direction.into(), jumps as u8).into()))
+  })
+}
+
+#[no_mangle]
+pub extern "C" fn konane_move_create_pass() -> *mut KonaneMove {
+  Box::into_raw(Box::new(::Move::Pass.into()))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_move_create_jump_chain(game_ref: *const KonaneGame,
+                                                       source_position_ref: *const KonanePosition,
+                                                       target_positions_ref: *const *const KonanePosition,
+                                                       target_positions_count: libc::size_t)
+                                                       -> *mut KonaneMove {
+  let mut result = ptr::null_mut();
+  if let Some(&initial_game) = game_ref.as_ref() {
+    if let Some(&source_position) = source_position_ref.as_ref() {
+      if !target_positions_ref.is_null() {
+        let game: ::Game = initial_game.into();
+        let target_positions: Box<iter::Iterator<Item = ::Position>> =
+          Box::new(slice::from_raw_parts(target_positions_ref, target_positions_count as usize)
+            .into_iter()
+            .flat_map(|&target_position_ref: &*const KonanePosition| {
+              target_position_ref.as_ref().into_iter()
+            })
+            .map(|&target_position: &KonanePosition| target_position.into()));
+        if let Ok(mv) = game.jump_chain(source_position.into(), target_positions) {
+          result = Box::into_raw(Box::new(mv.into()));
+        }
+      }
+    }
+  }
+  result
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_move_destroy(move_ref: *mut KonaneMove) {
+  if !move_ref.is_null() {
+    let move_box = Box::from_raw(move_ref);
+    drop(move_box);
+  }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_move_is_removal(move_ref: *const KonaneMove) -> bool {
+  let mut result = false;
+  if let Some(&initial_move) = move_ref.as_ref() {
+    let mv: ::Move = initial_move.into();
+    result = mv.is_removal();
+  }
+  result
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn konane_move_get_source(move_ref: *const KonaneMove) -> *mut KonanePosition {
+  move_ref.as_ref().map_or_else(ptr::null_mut, |&initial_move| {
+    let mv: ::Move = initial_move.into();
+    mv.source().map_or_else(ptr::null_mut, |source| Box::into_raw(Box::new(source.into())))
+  })
+}
+
 #[no_mangle]
 pub extern "C" fn konane_position_create(x: libc::uint8_t, y: libc::uint8_t) -> *mut KonanePosition {
   ::Position::new(x as u8, y as u8).map_or_else(ptr::null_mut,
@@ -161,7 +433,7 @@ pub extern "C" fn konane_position_create(x: libc::uint8_t, y: libc::uint8_t) ->
 
 #[no_mangle]
 pub unsafe extern "C" fn konane_position_destroy(position_ref: *mut KonanePosition) {
-  if position_ref.is_null() {
+  if !position_ref.is_null() {
     let position_box = Box::from_raw(position_ref);
     drop(position_box);
   }
@@ -169,7 +441,7 @@ pub unsafe extern "C" fn konane_position_destroy(position_ref: *mut KonanePositi
 
 #[no_mangle]
 pub unsafe extern "C" fn konane_position_get_x(position_ref: *const KonanePosition) -> libc::uint8_t {
-  let mut result = 10;
+  let mut result = ::MAX_DIMENSION;
   if let Some(&initial_position) = position_ref.as_ref() {
     let position: ::Position = initial_position.into();
     result = position.x() as libc::uint8_t;
@@ -179,7 +451,7 @@ pub unsafe extern "C" fn konane_position_get_x(position_ref: *const KonanePositi
 
 #[no_mangle]
 pub unsafe extern "C" fn konane_position_get_y(position_ref: *const KonanePosition) -> libc::uint8_t {
-  let mut result = 10;
+  let mut result = ::MAX_DIMENSION;
   if let Some(&initial_position) = position_ref.as_ref() {
     let position: ::Position = initial_position.into();
     result = position.y() as libc::uint8_t;
@@ -191,10 +463,10 @@ pub unsafe extern "C" fn konane_position_get_y(position_ref: *const KonanePositi
 pub unsafe extern "C" fn konane_position_set_x(position_ref: *mut KonanePosition,
                                                x: libc::uint8_t)
                                                -> libc::uint8_t {
-  let mut result = 10;
+  let mut result = ::MAX_DIMENSION;
   if let Some(&initial_position) = position_ref.as_ref() {
     let mut position: ::Position = initial_position.into();
-    result = position.set_x(x as u8).unwrap_or(10) as libc::uint8_t;
+    result = position.set_x(x as u8).unwrap_or(::MAX_DIMENSION) as libc::uint8_t;
     ptr::write(position_ref, position.into());
   }
   result
@@ -204,10 +476,10 @@ pub unsafe extern "C" fn konane_position_set_x(position_ref: *mut KonanePosition
 pub unsafe extern "C" fn konane_position_set_y(position_ref: *mut KonanePosition,
                                                y: libc::uint8_t)
                                                -> libc::uint8_t {
-  let mut result = 10;
+  let mut result = ::MAX_DIMENSION;
   if let Some(&initial_position) = position_ref.as_ref() {
     let mut position: ::Position = initial_position.into();
-    result = position.set_y(y as u8).unwrap_or(10) as libc::uint8_t;
+    result = position.set_y(y as u8).unwrap_or(::MAX_DIMENSION) as libc::uint8_t;
     ptr::write(position_ref, position.into());
   }
   result
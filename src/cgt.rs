@@ -0,0 +1,667 @@
+// cgt.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// In combinatorial game values black is Left and white is Right, so positive values favour black.  A `Value`
+// is always held in canonical form (dominated options removed and reversible options bypassed), which makes
+// two values equal exactly when their canonical forms are identical.  Values are ordered only partially: two
+// games can be confused with each other, in which case `partial_cmp` returns `None`.
+//
+// Computing a value explores every reachable position of each independent region (see
+// `Papamu::regions`), so it is only practical for small or nearly finished boards.  The values describe
+// normal play, where the player left without a move loses; they are not defined under the misère or
+// passing rules.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::{fmt, iter, ops};
+
+use {Bitboard, Game, GameState, LegalMoves, Move, Occupancy, Papamu, RuleSet, turn, zobrist};
+
+// A dyadic rational `numerator / 2^exponent`, always stored in lowest terms.
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+pub struct Dyadic {
+  numerator: i64,
+  exponent: u32,
+}
+
+impl Dyadic {
+  pub fn new(numerator: i64, exponent: u32) -> Dyadic {
+    let mut dyadic = Dyadic {
+      numerator: numerator,
+      exponent: exponent,
+    };
+    while dyadic.exponent > 0 && dyadic.numerator % 2 == 0 {
+      dyadic.numerator /= 2;
+      dyadic.exponent -= 1;
+    }
+    dyadic
+  }
+
+  pub fn integer(value: i64) -> Dyadic { Dyadic::new(value, 0) }
+
+  pub fn numerator(&self) -> i64 { self.numerator }
+
+  pub fn exponent(&self) -> u32 { self.exponent }
+
+  pub fn is_integer(&self) -> bool { self.exponent == 0 }
+
+  pub fn to_f64(&self) -> f64 { self.numerator as f64 / (1u64 << self.exponent) as f64 }
+
+  fn scaled(&self, exponent: u32) -> i64 { self.numerator << (exponent - self.exponent) }
+
+  // The floor of this number times `2^exponent`.
+  fn floor_scaled(&self, exponent: u32) -> i64 {
+    if exponent >= self.exponent {
+      self.numerator << (exponent - self.exponent)
+    } else {
+      self.numerator >> (self.exponent - exponent)
+    }
+  }
+
+  fn floor(&self) -> i64 { self.floor_scaled(0) }
+
+  fn ceil(&self) -> i64 { -(-*self).floor() }
+
+  // The simplest number strictly between the bounds, where `None` leaves that side unbounded.
+  fn simplest_between(lower: Option<Dyadic>, upper: Option<Dyadic>) -> Dyadic {
+    let zero = Dyadic::integer(0);
+    match (lower, upper) {
+      (Some(lower), _) if lower >= zero => {
+        let integer = Dyadic::integer(lower.floor() + 1);
+        if upper.map_or(true, |upper| integer < upper) {
+          return integer;
+        }
+      },
+      (_, Some(upper)) if upper <= zero => {
+        let integer = Dyadic::integer(upper.ceil() - 1);
+        if lower.map_or(true, |lower| integer > lower) {
+          return integer;
+        }
+      },
+      _ => return zero,
+    }
+    // No integer fits, so both bounds are present and lie within one unit interval; take the candidate with
+    // the smallest denominator.
+    let (lower, upper) = (lower.unwrap_or_else(|| unreachable!()), upper.unwrap_or_else(|| unreachable!()));
+    let mut exponent = 1;
+    loop {
+      let candidate = Dyadic::new(lower.floor_scaled(exponent) + 1, exponent);
+      if candidate < upper {
+        return candidate;
+      }
+      exponent += 1;
+    }
+  }
+}
+
+fn cmp_max(a: u32, b: u32) -> u32 { if a > b { a } else { b } }
+
+impl Ord for Dyadic {
+  fn cmp(&self, other: &Dyadic) -> Ordering {
+    let exponent = cmp_max(self.exponent, other.exponent);
+    self.scaled(exponent).cmp(&other.scaled(exponent))
+  }
+}
+
+impl PartialOrd for Dyadic {
+  fn partial_cmp(&self, other: &Dyadic) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl ops::Add for Dyadic {
+  type Output = Dyadic;
+
+  fn add(self, other: Dyadic) -> Dyadic {
+    let exponent = cmp_max(self.exponent, other.exponent);
+    Dyadic::new(self.scaled(exponent) + other.scaled(exponent), exponent)
+  }
+}
+
+impl ops::Neg for Dyadic {
+  type Output = Dyadic;
+
+  fn neg(self) -> Dyadic {
+    Dyadic {
+      numerator: -self.numerator,
+      exponent: self.exponent,
+    }
+  }
+}
+
+impl ops::Sub for Dyadic {
+  type Output = Dyadic;
+
+  fn sub(self, other: Dyadic) -> Dyadic { self + -other }
+}
+
+impl fmt::Display for Dyadic {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    if self.is_integer() {
+      write!(fmtr, "{}", self.numerator)
+    } else {
+      write!(fmtr, "{}/{}", self.numerator, 1u64 << self.exponent)
+    }
+  }
+}
+
+// The recognisable shapes a canonical form can take.
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+pub enum Form {
+  // A number `x`.
+  Number(Dyadic),
+  // `x + *n` for some `n >= 1`.
+  Nimber(Dyadic, u32),
+  // `x + k.↑`, plus `*` when the flag is set, for some nonzero `k`; negative `k` counts downs.
+  Ups(Dyadic, i32, bool),
+  // `x ± y` for some positive `y`, that is `{x + y | x - y}`.
+  Switch(Dyadic, Dyadic),
+  Other,
+}
+
+// The largest multiple of ↑ that `Value::form` looks for.
+const MAX_UPS: i32 = 8;
+
+// The options are shared between clones, so a game and every position that reaches it hold one copy.
+#[derive(Clone,PartialEq,Eq,Hash)]
+pub struct Value(Rc<Options>);
+
+#[derive(PartialEq,Eq,Hash)]
+struct Options {
+  left: Vec<Value>,
+  right: Vec<Value>,
+  number: Option<Dyadic>,
+}
+
+impl Value {
+  // The canonical form of `{left | right}`; the options may be given in any order and may dominate or
+  // reverse through one another.
+  pub fn new(left: Vec<Value>, right: Vec<Value>) -> Value {
+    if left.iter().chain(&right).all(Value::is_number) {
+      let lower = left.iter().filter_map(Value::as_number).max();
+      let upper = right.iter().filter_map(Value::as_number).min();
+      match (lower, upper) {
+        (Some(lower), Some(upper)) if lower >= upper => {
+          return Value::raw(vec![Value::number(lower)], vec![Value::number(upper)])
+        },
+        _ => return Value::number(Dyadic::simplest_between(lower, upper)),
+      }
+    }
+    let (mut left, mut right) = (left, right);
+    loop {
+      remove_dominated(&mut left, |a, b| a.at_most(b));
+      remove_dominated(&mut right, |a, b| b.at_most(a));
+      let game = Value::raw(left.clone(), right.clone());
+      let (new_left, left_changed) = bypass_reversible(&left, |option| {
+        option.0.right.iter().find(|reversing| reversing.at_most(&game)).map(|reversing| &reversing.0.left)
+      });
+      let (new_right, right_changed) = bypass_reversible(&right, |option| {
+        option.0.left.iter().find(|reversing| game.at_most(reversing)).map(|reversing| &reversing.0.right)
+      });
+      if !left_changed && !right_changed {
+        return game;
+      }
+      left = new_left;
+      right = new_right;
+    }
+  }
+
+  // Builds a form whose options are already canonical and free of dominated and reversible options.
+  fn raw(mut left: Vec<Value>, mut right: Vec<Value>) -> Value {
+    left.sort_by(Value::structural_cmp);
+    right.sort_by(Value::structural_cmp);
+    let number = if left.len() <= 1 && right.len() <= 1 && left.iter().chain(&right).all(Value::is_number) {
+      let lower = left.first().and_then(Value::as_number);
+      let upper = right.first().and_then(Value::as_number);
+      match (lower, upper) {
+        (Some(lower), Some(upper)) if lower >= upper => None,
+        _ => Some(Dyadic::simplest_between(lower, upper)),
+      }
+    } else {
+      None
+    };
+    Value(Rc::new(Options {
+      left: left,
+      right: right,
+      number: number,
+    }))
+  }
+
+  pub fn zero() -> Value { Value::raw(Vec::new(), Vec::new()) }
+
+  pub fn integer(value: i64) -> Value { Value::number(Dyadic::integer(value)) }
+
+  pub fn number(value: Dyadic) -> Value {
+    let zero = Dyadic::integer(0);
+    if value == zero {
+      Value::zero()
+    } else if value.is_integer() && value > zero {
+      Value::raw(vec![Value::number(value - Dyadic::integer(1))], Vec::new())
+    } else if value.is_integer() {
+      Value::raw(Vec::new(), vec![Value::number(value + Dyadic::integer(1))])
+    } else {
+      let step = Dyadic::new(1, value.exponent);
+      Value::raw(vec![Value::number(value - step)], vec![Value::number(value + step)])
+    }
+  }
+
+  pub fn nimber(heap: u32) -> Value {
+    let options: Vec<Value> = (0..heap).map(Value::nimber).collect();
+    Value::raw(options.clone(), options)
+  }
+
+  pub fn star() -> Value { Value::nimber(1) }
+
+  pub fn up() -> Value { Value::raw(vec![Value::zero()], vec![Value::star()]) }
+
+  pub fn down() -> Value { -Value::up() }
+
+  // The switch `{high | low}` between two numbers; if `high` is not above `low` this is just a number.
+  pub fn switch(high: Dyadic, low: Dyadic) -> Value {
+    Value::new(vec![Value::number(high)], vec![Value::number(low)])
+  }
+
+  pub fn left_options(&self) -> &[Value] { &self.0.left }
+
+  pub fn right_options(&self) -> &[Value] { &self.0.right }
+
+  pub fn is_number(&self) -> bool { self.0.number.is_some() }
+
+  pub fn as_number(&self) -> Option<Dyadic> { self.0.number }
+
+  pub fn is_zero(&self) -> bool { self.0.left.is_empty() && self.0.right.is_empty() }
+
+  // The best number Left can reach by moving first, if both sides play on until a number is reached.
+  pub fn left_stop(&self) -> Dyadic {
+    match self.0.number {
+      Some(number) => number,
+      None => self.0.left.iter().map(Value::right_stop).max().unwrap_or_else(|| unreachable!()),
+    }
+  }
+
+  pub fn right_stop(&self) -> Dyadic {
+    match self.0.number {
+      Some(number) => number,
+      None => self.0.right.iter().map(Value::left_stop).min().unwrap_or_else(|| unreachable!()),
+    }
+  }
+
+  // True for games that are not numbers but lie strictly between every negative and every positive number.
+  pub fn is_infinitesimal(&self) -> bool {
+    let zero = Dyadic::integer(0);
+    !self.is_number() && self.left_stop() == zero && self.right_stop() == zero
+  }
+
+  pub fn form(&self) -> Form {
+    if let Some(number) = self.0.number {
+      return Form::Number(number);
+    }
+    if let Some((number, heap)) = self.number_plus_nimber() {
+      return Form::Nimber(number, heap);
+    }
+    let (left_stop, right_stop) = (self.left_stop(), self.right_stop());
+    if left_stop == right_stop {
+      let number = Value::number(left_stop);
+      let mut ups = Value::zero();
+      for count in 1..MAX_UPS + 1 {
+        ups = ups + Value::up();
+        for &starred in &[false, true] {
+          let infinitesimal = if starred { ups.clone() + Value::star() } else { ups.clone() };
+          if *self == number.clone() + infinitesimal.clone() {
+            return Form::Ups(left_stop, count, starred);
+          }
+          if *self == number.clone() + -infinitesimal {
+            return Form::Ups(left_stop, -count, starred);
+          }
+        }
+      }
+    }
+    if self.0.left.len() == 1 && self.0.right.len() == 1 {
+      if let (Some(high), Some(low)) = (self.0.left[0].0.number, self.0.right[0].0.number) {
+        let mean = Dyadic::new((high + low).numerator, (high + low).exponent + 1);
+        return Form::Switch(mean, high - mean);
+      }
+    }
+    Form::Other
+  }
+
+  // Recognises `x + *n`, whose canonical form is `{x, x*, ..., x*(n-1) | x, x*, ..., x*(n-1)}`.
+  fn number_plus_nimber(&self) -> Option<(Dyadic, u32)> {
+    if let Some(number) = self.0.number {
+      return Some((number, 0));
+    }
+    if self.0.left != self.0.right {
+      return None;
+    }
+    let parts: Option<Vec<(Dyadic, u32)>> = self.0.left.iter().map(Value::number_plus_nimber).collect();
+    let mut parts = match parts {
+      Some(parts) => parts,
+      None => return None,
+    };
+    parts.sort_by_key(|&(_, heap)| heap);
+    let number = parts[0].0;
+    if parts.iter().enumerate().all(|(heap, &part)| part == (number, heap as u32)) {
+      Some((number, parts.len() as u32))
+    } else {
+      None
+    }
+  }
+
+  fn at_most(&self, other: &Value) -> bool {
+    if let (Some(mine), Some(theirs)) = (self.0.number, other.0.number) {
+      return mine <= theirs;
+    }
+    !self.0.left.iter().any(|option| other.at_most(option)) &&
+    !other.0.right.iter().any(|option| option.at_most(self))
+  }
+
+  pub fn is_confused_with(&self, other: &Value) -> bool { !self.at_most(other) && !other.at_most(self) }
+
+  // Who wins when `first` moves first: Left wins positive games, Right negative ones, the second player
+  // zero games and the first player games confused with zero.
+  pub fn winner(&self, first: Occupancy) -> Occupancy {
+    let zero = Value::zero();
+    match (self.at_most(&zero), zero.at_most(self)) {
+      (true, true) if first.is_black() => Occupancy::White,
+      (true, true) => Occupancy::Black,
+      (false, true) => Occupancy::Black,
+      (true, false) => Occupancy::White,
+      (false, false) => first,
+    }
+  }
+
+  // An arbitrary but fixed total order on forms, used to keep options sorted.
+  fn structural_cmp(&self, other: &Value) -> Ordering {
+    let by_length = (self.0.left.len(), self.0.right.len()).cmp(&(other.0.left.len(), other.0.right.len()));
+    let by_options = self.0.left
+                         .iter()
+                         .chain(&self.0.right)
+                         .zip(other.0.left.iter().chain(&other.0.right))
+                         .map(|(mine, theirs)| mine.structural_cmp(theirs))
+                         .find(|&ordering| ordering != Ordering::Equal);
+    by_length.then(by_options.unwrap_or(Ordering::Equal))
+  }
+}
+
+fn remove_dominated<F: Fn(&Value, &Value) -> bool>(options: &mut Vec<Value>, dominated_by: F) {
+  options.sort_by(Value::structural_cmp);
+  options.dedup();
+  let mut index = 0;
+  while index < options.len() {
+    let dominated = (0..options.len())
+                      .any(|other| other != index && dominated_by(&options[index], &options[other]));
+    if dominated {
+      options.remove(index);
+    } else {
+      index += 1;
+    }
+  }
+}
+
+fn bypass_reversible<'a, F>(options: &'a [Value], reversal: F) -> (Vec<Value>, bool)
+  where F: Fn(&'a Value) -> Option<&'a Vec<Value>>
+{
+  let mut bypassed = Vec::new();
+  let mut changed = false;
+  for option in options {
+    match reversal(option) {
+      Some(replacements) => {
+        bypassed.extend(replacements.iter().cloned());
+        changed = true;
+      },
+      None => bypassed.push(option.clone()),
+    }
+  }
+  (bypassed, changed)
+}
+
+impl PartialOrd for Value {
+  fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+    match (self.at_most(other), other.at_most(self)) {
+      (true, true) => Some(Ordering::Equal),
+      (true, false) => Some(Ordering::Less),
+      (false, true) => Some(Ordering::Greater),
+      (false, false) => None,
+    }
+  }
+}
+
+impl ops::Neg for Value {
+  type Output = Value;
+
+  fn neg(self) -> Value {
+    if let Some(number) = self.0.number {
+      return Value::number(-number);
+    }
+    Value::raw(self.0.right.iter().cloned().map(ops::Neg::neg).collect(),
+               self.0.left.iter().cloned().map(ops::Neg::neg).collect())
+  }
+}
+
+impl ops::Add for Value {
+  type Output = Value;
+
+  fn add(self, other: Value) -> Value {
+    if let (Some(mine), Some(theirs)) = (self.0.number, other.0.number) {
+      return Value::number(mine + theirs);
+    }
+    if self.is_zero() {
+      return other;
+    }
+    if other.is_zero() {
+      return self;
+    }
+    let left = self.0.left
+                   .iter()
+                   .map(|option| option.clone() + other.clone())
+                   .chain(other.0.left.iter().map(|option| self.clone() + option.clone()))
+                   .collect();
+    let right = self.0.right
+                    .iter()
+                    .map(|option| option.clone() + other.clone())
+                    .chain(other.0.right.iter().map(|option| self.clone() + option.clone()))
+                    .collect();
+    Value::new(left, right)
+  }
+}
+
+impl ops::Sub for Value {
+  type Output = Value;
+
+  fn sub(self, other: Value) -> Value { self + -other }
+}
+
+impl iter::Sum for Value {
+  fn sum<Vs: Iterator<Item = Value>>(values: Vs) -> Value {
+    values.fold(Value::zero(), |sum, value| sum + value)
+  }
+}
+
+fn write_options(fmtr: &mut fmt::Formatter, options: &[Value]) -> fmt::Result {
+  for (index, option) in options.iter().enumerate() {
+    if index > 0 {
+      try!(write!(fmtr, ","));
+    }
+    if fmtr.alternate() {
+      try!(write!(fmtr, "{:#}", option));
+    } else {
+      try!(write!(fmtr, "{}", option));
+    }
+  }
+  Ok(())
+}
+
+// Written the way CGSuite writes values: `{:#}` uses ↑, ↓ and ±, and the plain form `^`, `v` and `+-`.
+impl fmt::Display for Value {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    let zero = Dyadic::integer(0);
+    match self.form() {
+      Form::Number(number) => write!(fmtr, "{}", number),
+      Form::Nimber(number, heap) => {
+        if number != zero {
+          try!(write!(fmtr, "{}", number));
+        }
+        if heap == 1 { write!(fmtr, "*") } else { write!(fmtr, "*{}", heap) }
+      },
+      Form::Ups(number, count, starred) => {
+        if number != zero {
+          try!(write!(fmtr, "{}", number));
+        }
+        let arrow = match (count > 0, fmtr.alternate()) {
+          (true, true) => "↑",
+          (true, false) => "^",
+          (false, true) => "↓",
+          (false, false) => "v",
+        };
+        match count.abs() {
+          1 => try!(write!(fmtr, "{}", arrow)),
+          2 => try!(write!(fmtr, "{}{}", arrow, arrow)),
+          count => try!(write!(fmtr, "{}{}", arrow, count)),
+        }
+        if starred { write!(fmtr, "*") } else { Ok(()) }
+      },
+      Form::Switch(mean, radius) => {
+        if mean != zero {
+          try!(write!(fmtr, "{}", mean));
+        }
+        write!(fmtr, "{}{}", if fmtr.alternate() { "±" } else { "+-" }, radius)
+      },
+      Form::Other => {
+        try!(write!(fmtr, "{{"));
+        try!(write_options(fmtr, &self.0.left));
+        try!(write!(fmtr, "|"));
+        try!(write_options(fmtr, &self.0.right));
+        write!(fmtr, "}}")
+      },
+    }
+  }
+}
+
+impl fmt::Debug for Value {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result { write!(fmtr, "Value({})", self) }
+}
+
+// Memoises values by board, since the same position is usually reached along many different lines, and
+// splits each board into independent regions so that a sum is evaluated one part at a time.  Boards that
+// fit in a `Bitboard` are remembered by its pieces alone; larger ones by their Zobrist keys, with the
+// board kept to tell apart positions whose keys collide.  Far fewer values than positions turn up, so
+// each is kept once and shared between all the positions that have it.
+struct Evaluator {
+  rules: RuleSet,
+  values: HashMap<(u8, u8, u128, u128), Value>,
+  large_values: HashMap<u64, Vec<(Papamu, Value)>>,
+  distinct: HashSet<Value>,
+}
+
+impl Evaluator {
+  fn new(rules: RuleSet) -> Evaluator {
+    Evaluator {
+      rules: rules,
+      values: HashMap::new(),
+      large_values: HashMap::new(),
+      distinct: HashSet::new(),
+    }
+  }
+
+  fn value(&mut self, papamu: &Papamu) -> Value {
+    if let Some(value) = self.remembered(papamu) {
+      return value;
+    }
+    let regions = papamu.regions();
+    let value = if regions.len() > 1 {
+      let mut sum = Value::zero();
+      for region in &regions {
+        sum = sum + self.value(region.papamu());
+      }
+      sum
+    } else {
+      let left = self.options(papamu, Occupancy::Black);
+      let right = self.options(papamu, Occupancy::White);
+      Value::new(left, right)
+    };
+    let value = match self.distinct.get(&value) {
+      Some(known) => known.clone(),
+      None => value,
+    };
+    self.distinct.insert(value.clone());
+    self.remember(papamu, value.clone());
+    value
+  }
+
+  fn remembered(&self, papamu: &Papamu) -> Option<Value> {
+    match papamu.bitboard() {
+      Some(bitboard) => self.values.get(&bitboard_key(&bitboard)).cloned(),
+      None => {
+        self.large_values
+            .get(&zobrist::papamu_key(papamu))
+            .and_then(|values| values.iter().find(|&&(ref board, _)| board == papamu))
+            .map(|&(_, ref value)| value.clone())
+      },
+    }
+  }
+
+  fn remember(&mut self, papamu: &Papamu, value: Value) {
+    match papamu.bitboard() {
+      Some(bitboard) => {
+        self.values.insert(bitboard_key(&bitboard), value);
+      },
+      None => {
+        self.large_values.entry(zobrist::papamu_key(papamu)).or_insert_with(Vec::new).push((*papamu, value));
+      },
+    }
+  }
+
+  fn options(&mut self, papamu: &Papamu, player: Occupancy) -> Vec<Value> {
+    let moves: Vec<Move> = LegalMoves::new(papamu, player, self.rules).collect();
+    moves.into_iter()
+         .map(|mv| {
+           let next = self.after(papamu, player, mv);
+           self.value(&next)
+         })
+         .collect()
+  }
+
+  fn after(&self, papamu: &Papamu, player: Occupancy, mv: Move) -> Papamu {
+    let result = if player.is_black() {
+      GameState::<turn::Black>::from_papamu(*papamu, 0, self.rules).next_turn(mv).map(|game| game.papamu)
+    } else {
+      GameState::<turn::White>::from_papamu(*papamu, 0, self.rules).next_turn(mv).map(|game| game.papamu)
+    };
+    result.unwrap_or_else(|_| unreachable!())
+  }
+}
+
+fn bitboard_key(bitboard: &Bitboard) -> (u8, u8, u128, u128) {
+  (bitboard.width(), bitboard.height(), bitboard.black(), bitboard.white())
+}
+
+impl Papamu {
+  // The value under the standard rules.
+  pub fn value(&self) -> Value { Evaluator::new(RuleSet::default()).value(self) }
+}
+
+impl<Tn: turn::Turn> GameState<Tn> {
+  // The value under this game's rules, or `None` for rules that are not normal play.
+  pub fn value(&self) -> Option<Value> {
+    if self.rules.misere || self.rules.passing {
+      None
+    } else {
+      Some(Evaluator::new(self.rules).value(&self.papamu))
+    }
+  }
+}
+
+impl Game {
+  pub fn value(&self) -> Option<Value> { self.by_color_ref(GameState::value, GameState::value) }
+}
@@ -0,0 +1,190 @@
+// diagnose.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use {Game, GameState, Move, Occupancy, Position, errors, turn};
+
+// A single broken rule: the error `next_turn` would report for it, the squares involved, and for a
+// multi-jump the 1-based number of the jump at which it happens.
+#[derive(Debug)]
+pub struct Violation {
+  pub kind: errors::Error,
+  pub positions: Vec<Position>,
+  pub jump: Option<usize>,
+}
+
+impl Violation {
+  fn new(kind: errors::Error, positions: Vec<Position>) -> Violation {
+    Violation {
+      kind: kind,
+      positions: positions,
+      jump: None,
+    }
+  }
+
+  fn at_jump(kind: errors::Error, positions: Vec<Position>, jump: usize) -> Violation {
+    Violation {
+      kind: kind,
+      positions: positions,
+      jump: Some(jump),
+    }
+  }
+}
+
+impl<Tn: turn::Turn> GameState<Tn> {
+  // Every rule `mv` breaks, in the order a player would meet them; empty exactly when `next_turn` would
+  // accept the move.
+  pub fn diagnose(&self, mv: Move) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    if let Some(outcome) = self.outcome() {
+      let kind = errors::Error::GameOver {
+        winner: outcome.winner,
+        plies: outcome.plies,
+      };
+      violations.push(Violation::new(kind, Vec::new()));
+    }
+    match mv {
+      Move::Remove(target) => self.diagnose_removal(target, &mut violations),
+      Move::Pass => self.diagnose_pass(&mut violations),
+      Move::Jump { source, .. } |
+      Move::Path { source, .. } => self.diagnose_jump(source, mv, &mut violations),
+    }
+    violations
+  }
+
+  fn diagnose_source(&self, source: Position, violations: &mut Vec<Violation>) -> bool {
+    let papamu = &self.papamu;
+    // `next_turn` treats a square off the board as an empty one.
+    if !papamu.contains(source) {
+      violations.push(Violation::new(errors::Error::EmptySource { source_pos: source }, vec![source]));
+      return false;
+    }
+    if papamu[source].is_empty() {
+      violations.push(Violation::new(errors::Error::EmptySource { source_pos: source }, vec![source]));
+    } else if papamu[source] != Tn::piece_type() {
+      let kind = errors::Error::WrongColor {
+        source_occ: papamu[source],
+        source_pos: source,
+        desired_occ: Tn::piece_type(),
+      };
+      violations.push(Violation::new(kind, vec![source]));
+    }
+    true
+  }
+
+  fn diagnose_removal(&self, target: Position, violations: &mut Vec<Violation>) {
+    if !self.diagnose_source(target, violations) {
+      return;
+    }
+    let papamu = &self.papamu;
+    let removed = papamu.positions().find(|&pos| papamu[pos].is_empty());
+    let kind = errors::Error::IllegalRemoval {
+      source_occ: papamu[target],
+      source_pos: target,
+    };
+    match (papamu.empty_count(), removed) {
+      (0, _) if !self.rules.opening.allows(papamu, target) => {
+        violations.push(Violation::new(kind, vec![target]));
+      },
+      (1, Some(removed)) if !removed.is_adjacent(target) => {
+        violations.push(Violation::new(kind, vec![target, removed]));
+      },
+      (0, _) | (1, _) => {},
+      _ => {
+        violations.push(Violation::new(errors::Error::OpeningComplete { source_pos: target }, vec![target]))
+      },
+    }
+  }
+
+  fn diagnose_pass(&self, violations: &mut Vec<Violation>) {
+    if self.legal_moves().any(|mv| mv.is_pass()) {
+      return;
+    }
+    let sources = self.legal_moves().filter_map(|mv| mv.source()).fold(Vec::new(), |mut sources, source| {
+      if !sources.contains(&source) {
+        sources.push(source);
+      }
+      sources
+    });
+    violations.push(Violation::new(errors::Error::IllegalPass { player: Tn::piece_type() }, sources));
+  }
+
+  fn diagnose_jump(&self, source: Position, mv: Move, violations: &mut Vec<Violation>) {
+    if self.is_opening() {
+      violations.push(Violation::new(errors::Error::OpeningIncomplete { source_pos: source }, vec![source]));
+    }
+    if !self.diagnose_source(source, violations) {
+      return;
+    }
+    if mv.jumps() == 0 {
+      let kind = errors::Error::NoTargets {
+        source_occ: self[source],
+        source_pos: source,
+      };
+      violations.push(Violation::new(kind, vec![source]));
+    }
+    let player = Tn::piece_type();
+    let mut papamu = self.papamu;
+    let mut current = source;
+    let mut previous = None;
+    for (index, direction) in mv.directions().enumerate() {
+      let jump = index + 1;
+      if !self.rules.turning_jumps && previous.map_or(false, |previous| previous != direction) {
+        let kind = errors::Error::TurningJump {
+          source_occ: self[source],
+          source_pos: source,
+          turn_pos: current,
+        };
+        violations.push(Violation::at_jump(kind, vec![source, current], jump));
+      }
+      previous = Some(direction);
+      let (mid, target) = match (papamu.offset(current, direction, 1), papamu.offset(current, direction, 2)) {
+        (Some(mid), Some(target)) => (mid, target),
+        _ => {
+          let kind = errors::Error::OffBoardJump {
+            source_occ: self[source],
+            source_pos: current,
+            direction: direction,
+          };
+          violations.push(Violation::at_jump(kind, vec![current], jump));
+          return;
+        },
+      };
+      if papamu[target].is_occupied() {
+        let kind = errors::Error::OccupiedTarget { target_pos: target };
+        violations.push(Violation::at_jump(kind, vec![target], jump));
+      }
+      if papamu[mid].is_empty() || papamu[mid] == player {
+        let kind = errors::Error::IllegalJump {
+          source_occ: self[source],
+          source_pos: current,
+          mid_occ: papamu[mid],
+          mid_pos: mid,
+          target_pos: target,
+        };
+        violations.push(Violation::at_jump(kind, vec![current, mid, target], jump));
+      }
+      papamu[current] = Occupancy::Empty;
+      papamu[mid] = Occupancy::Empty;
+      papamu[target] = player;
+      current = target;
+    }
+  }
+}
+
+impl Game {
+  pub fn diagnose(&self, mv: Move) -> Vec<Violation> {
+    self.by_color_ref(|game| game.diagnose(mv), |game| game.diagnose(mv))
+  }
+}
@@ -0,0 +1,299 @@
+// diagram.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// A diagram draws the highest rank first, with each rank's number on the left and the file letters
+// underneath, so that every square reads off as the `(char, u8)` form of its `Position`:
+//
+//  3 o x o x
+//  2 x o(.)o
+//  1 o x o x
+//  0 x o x o
+//    A B C D
+//
+// Highlighted squares are wrapped in parentheses.  Each square owns only the character before it, so when
+// two highlighted squares sit side by side the second one's "(" also closes the first, as in "(x(o)x".
+// The parser reads the parentheses as separators and ignores them.  A `Game` diagram adds a final "black
+// to move" or "white to move" line.
+
+use std::{fmt, str};
+
+use {Game, GameState, MAX_DIMENSION, Move, Occupancy, Papamu, Position, RuleSet, errors};
+
+const ASCII_STONES: [char; 3] = ['o', 'x', '.'];
+
+const UNICODE_STONES: [char; 3] = ['○', '●', '·'];
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub struct Diagram<'a> {
+  papamu: &'a Papamu,
+  to_move: Option<Occupancy>,
+  unicode: bool,
+  highlights: Vec<Position>,
+}
+
+impl<'a> Diagram<'a> {
+  pub fn unicode(mut self, unicode: bool) -> Diagram<'a> {
+    self.unicode = unicode;
+    self
+  }
+
+  pub fn highlight(mut self, pos: Position) -> Diagram<'a> {
+    if self.papamu.contains(pos) && !self.highlights.contains(&pos) {
+      self.highlights.push(pos);
+    }
+    self
+  }
+
+  pub fn highlight_move(self, mv: Move) -> Diagram<'a> {
+    match mv {
+      Move::Remove(target) => self.highlight(target),
+      Move::Pass => self,
+      Move::Jump { source, .. } |
+      Move::Path { source, .. } => {
+        let mut diagram = self.highlight(source);
+        let mut current = source;
+        for direction in mv.directions() {
+          current = match current.offset(direction, 2) {
+            Some(target) => target,
+            None => break,
+          };
+          diagram = diagram.highlight(current);
+        }
+        diagram
+      },
+    }
+  }
+
+  fn stone(&self, occ: Occupancy) -> char {
+    let stones = if self.unicode { &UNICODE_STONES } else { &ASCII_STONES };
+    match occ {
+      Occupancy::White => stones[0],
+      Occupancy::Black => stones[1],
+      Occupancy::Empty => stones[2],
+    }
+  }
+}
+
+impl<'a> fmt::Display for Diagram<'a> {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    for y in (0..self.papamu.height()).rev() {
+      try!(write!(fmtr, "{:>2}", y));
+      let mut after_highlight = false;
+      for x in 0..self.papamu.width() {
+        let pos = Position { x: x, y: y };
+        let highlighted = self.highlights.contains(&pos);
+        let separator = if highlighted {
+          '('
+        } else if after_highlight {
+          ')'
+        } else {
+          ' '
+        };
+        try!(write!(fmtr, "{}{}", separator, self.stone(self.papamu[pos])));
+        after_highlight = highlighted;
+      }
+      try!(writeln!(fmtr, "{}", if after_highlight { ")" } else { "" }));
+    }
+    try!(write!(fmtr, "  "));
+    for x in 0..self.papamu.width() {
+      try!(write!(fmtr, " {}", (b'A' + x) as char));
+    }
+    match self.to_move {
+      Some(player) => write!(fmtr, "\n{} to move", player),
+      None => Ok(()),
+    }
+  }
+}
+
+impl Papamu {
+  pub fn diagram(&self) -> Diagram {
+    Diagram {
+      papamu: self,
+      to_move: None,
+      unicode: false,
+      highlights: Vec::new(),
+    }
+  }
+
+  pub fn from_diagram(text: &str) -> errors::Result<Papamu> {
+    let lines = diagram_lines(text);
+    read_grid(&lines)
+  }
+}
+
+impl Game {
+  pub fn diagram(&self) -> Diagram {
+    Diagram { to_move: Some(self.current_player()), ..self.papamu().diagram() }
+  }
+
+  pub fn from_diagram(text: &str) -> errors::Result<Game> {
+    let mut lines = diagram_lines(text);
+    let (line, to_move) = match lines.pop() {
+      Some(last) => last,
+      None => try!(Err(errors::Error::BadDiagram { line: 1, column: 1, expected: "a board diagram" })),
+    };
+    let papamu = try!(read_grid(&lines));
+    match to_move.trim() {
+      "black to move" => Ok(Game::Black(GameState::from_papamu(papamu, 0, RuleSet::default()))),
+      "white to move" => Ok(Game::White(GameState::from_papamu(papamu, 0, RuleSet::default()))),
+      _ => Err(errors::Error::BadDiagram {
+        line: line,
+        column: 1,
+        expected: "\"black to move\" or \"white to move\"",
+      }),
+    }
+  }
+}
+
+impl fmt::Display for Papamu {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    write!(fmtr, "{}", self.diagram().unicode(fmtr.alternate()))
+  }
+}
+
+impl fmt::Display for Game {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    write!(fmtr, "{}", self.diagram().unicode(fmtr.alternate()))
+  }
+}
+
+impl str::FromStr for Papamu {
+  type Err = errors::Error;
+
+  fn from_str(text: &str) -> errors::Result<Papamu> { Papamu::from_diagram(text) }
+}
+
+fn diagram_lines(text: &str) -> Vec<(usize, &str)> {
+  let mut lines: Vec<(usize, &str)> = text.lines()
+                                          .enumerate()
+                                          .map(|(index, line)| (index + 1, line.trim_right()))
+                                          .skip_while(|&(_, line)| line.is_empty())
+                                          .collect();
+  while lines.last().map_or(false, |&(_, line)| line.is_empty()) {
+    lines.pop();
+  }
+  lines
+}
+
+fn bad_diagram(line: usize, column: usize, expected: &'static str) -> errors::Error {
+  errors::Error::BadDiagram { line: line, column: column + 1, expected: expected }
+}
+
+fn read_stone(stone: char) -> Option<Occupancy> {
+  if stone == ASCII_STONES[0] || stone == UNICODE_STONES[0] || stone == 'O' {
+    Some(Occupancy::White)
+  } else if stone == ASCII_STONES[1] || stone == UNICODE_STONES[1] || stone == 'X' {
+    Some(Occupancy::Black)
+  } else if stone == ASCII_STONES[2] || stone == UNICODE_STONES[2] {
+    Some(Occupancy::Empty)
+  } else {
+    None
+  }
+}
+
+fn read_rank(line: usize, text: &str) -> errors::Result<(u32, Vec<Occupancy>)> {
+  let chars: Vec<char> = text.chars().collect();
+  let mut column = 0;
+  while column < chars.len() && chars[column] == ' ' {
+    column += 1;
+  }
+  let mut rank = None;
+  while column < chars.len() && chars[column].is_digit(10) {
+    let digit = chars[column].to_digit(10).unwrap_or_else(|| unreachable!());
+    rank = Some(rank.unwrap_or(0) * 10 + digit);
+    if rank >= Some(u32::from(MAX_DIMENSION)) {
+      try!(Err(bad_diagram(line, column, "a rank number below 26")))
+    }
+    column += 1;
+  }
+  let rank = try!(rank.ok_or_else(|| bad_diagram(line, column, "a rank number")));
+  let mut squares = Vec::new();
+  while column + 1 < chars.len() {
+    if chars[column] != ' ' && chars[column] != '(' && chars[column] != ')' {
+      try!(Err(bad_diagram(line, column, "a space or a highlight between squares")))
+    }
+    match read_stone(chars[column + 1]) {
+      Some(occ) => squares.push(occ),
+      None => try!(Err(bad_diagram(line, column + 1, "a stone or an empty square"))),
+    }
+    column += 2;
+  }
+  if column < chars.len() && chars[column] != ')' {
+    try!(Err(bad_diagram(line, column, "the end of the rank")))
+  }
+  Ok((rank, squares))
+}
+
+fn read_files(line: usize, text: &str, width: usize) -> errors::Result<()> {
+  let chars: Vec<char> = text.chars().collect();
+  let mut column = 0;
+  while column < chars.len() && chars[column] == ' ' {
+    column += 1;
+  }
+  for x in 0..width {
+    if x > 0 {
+      if chars.get(column) != Some(&' ') {
+        try!(Err(bad_diagram(line, column, "a space between file letters")))
+      }
+      column += 1;
+    }
+    if chars.get(column).map(|file| file.to_ascii_uppercase()) != Some((b'A' + x as u8) as char) {
+      try!(Err(bad_diagram(line, column, "the next file letter")))
+    }
+    column += 1;
+  }
+  if column != chars.len() {
+    try!(Err(bad_diagram(line, column, "the end of the file letters")))
+  }
+  Ok(())
+}
+
+fn read_grid(lines: &[(usize, &str)]) -> errors::Result<Papamu> {
+  let (&(files_line, files), ranks) = match lines.split_last() {
+    Some(split) if !split.1.is_empty() => split,
+    _ => {
+      let line = lines.last().map_or(1, |&(line, _)| line);
+      try!(Err(bad_diagram(line, 0, "ranks followed by file letters")))
+    },
+  };
+  if ranks.len() > usize::from(MAX_DIMENSION) {
+    try!(Err(bad_diagram(ranks[usize::from(MAX_DIMENSION)].0, 0, "at most 26 ranks")))
+  }
+  let height = ranks.len() as u8;
+  let mut rows = Vec::with_capacity(ranks.len());
+  for (index, &(line, text)) in ranks.iter().enumerate() {
+    let (rank, squares) = try!(read_rank(line, text));
+    if rank != u32::from(height) - 1 - index as u32 {
+      try!(Err(bad_diagram(line, 0, "the rank numbers counting down to 0")))
+    }
+    if squares.is_empty() || squares.len() > usize::from(MAX_DIMENSION) {
+      try!(Err(bad_diagram(line, 0, "between 1 and 26 squares in the rank")))
+    }
+    if rows.first().map_or(false, |first: &Vec<Occupancy>| first.len() != squares.len()) {
+      try!(Err(bad_diagram(line, 0, "the same number of squares as the first rank")))
+    }
+    rows.push(squares);
+  }
+  let width = rows[0].len() as u8;
+  try!(read_files(files_line, files, usize::from(width)));
+  let mut papamu = try!(Papamu::new(width, height).ok_or_else(|| bad_diagram(files_line, 0, "a board")));
+  for (row, y) in rows.iter().zip((0..height).rev()) {
+    for (x, &occ) in row.iter().enumerate() {
+      papamu[Position { x: x as u8, y: y }] = occ;
+    }
+  }
+  try!(papamu.check_parity());
+  Ok(papamu)
+}
@@ -0,0 +1,334 @@
+// errors.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Every error carries the squares and pieces involved as named fields, so callers can match on them
+// directly.  `code` and `name` identify each variant stably for the C API and for JSON; new variants only
+// ever get new codes, and existing codes are never reused.
+
+use std::{error, fmt, io, result};
+
+use {Direction, Occupancy, Position};
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+  IllegalTarget {
+    source_occ: Occupancy,
+    source_pos: Position,
+    target_pos: Position,
+  },
+  OccupiedTarget { target_pos: Position },
+  EmptySource { source_pos: Position },
+  WrongColor {
+    source_occ: Occupancy,
+    source_pos: Position,
+    desired_occ: Occupancy,
+  },
+  NoTargets {
+    source_occ: Occupancy,
+    source_pos: Position,
+  },
+  IllegalJump {
+    source_occ: Occupancy,
+    source_pos: Position,
+    mid_occ: Occupancy,
+    mid_pos: Position,
+    target_pos: Position,
+  },
+  IllegalRemoval {
+    source_occ: Occupancy,
+    source_pos: Position,
+  },
+  OpeningComplete { source_pos: Position },
+  OpeningIncomplete { source_pos: Position },
+  TurningJump {
+    source_occ: Occupancy,
+    source_pos: Position,
+    turn_pos: Position,
+  },
+  OffBoardJump {
+    source_occ: Occupancy,
+    source_pos: Position,
+    direction: Direction,
+  },
+  IllegalPass { player: Occupancy },
+  LongJumpPath { source_pos: Position },
+  GameOver { winner: Occupancy, plies: u32 },
+  BadNotation {
+    text: String,
+    column: usize,
+    expected: &'static str,
+  },
+  MisplacedPiece { occ: Occupancy, pos: Position },
+  BadBoardSize { width: u8, height: u8 },
+  OffBoard {
+    pos: Position,
+    width: u8,
+    height: u8,
+  },
+  EmptyTurn,
+  BadDiagram {
+    line: usize,
+    column: usize,
+    expected: &'static str,
+  },
+  BadKgn {
+    line: usize,
+    column: usize,
+    message: String,
+  },
+  Io { inner: io::Error },
+  NoSuchPly { ply: usize, plies: usize },
+  BadTablebase { expected: &'static str },
+}
+
+impl Error {
+  pub fn code(&self) -> u32 {
+    match *self {
+      Error::IllegalTarget { .. } => 1,
+      Error::OccupiedTarget { .. } => 2,
+      Error::EmptySource { .. } => 3,
+      Error::WrongColor { .. } => 4,
+      Error::NoTargets { .. } => 5,
+      Error::IllegalJump { .. } => 6,
+      Error::IllegalRemoval { .. } => 7,
+      Error::OpeningComplete { .. } => 8,
+      Error::OpeningIncomplete { .. } => 9,
+      Error::TurningJump { .. } => 10,
+      Error::OffBoardJump { .. } => 11,
+      Error::IllegalPass { .. } => 12,
+      Error::LongJumpPath { .. } => 13,
+      Error::GameOver { .. } => 14,
+      Error::BadNotation { .. } => 15,
+      Error::MisplacedPiece { .. } => 16,
+      Error::BadBoardSize { .. } => 17,
+      Error::OffBoard { .. } => 18,
+      Error::EmptyTurn => 19,
+      Error::BadDiagram { .. } => 20,
+      Error::BadKgn { .. } => 21,
+      Error::Io { .. } => 22,
+      Error::NoSuchPly { .. } => 23,
+      Error::BadTablebase { .. } => 24,
+    }
+  }
+
+  pub fn name(&self) -> &'static str {
+    match *self {
+      Error::IllegalTarget { .. } => "illegal-target",
+      Error::OccupiedTarget { .. } => "occupied-target",
+      Error::EmptySource { .. } => "empty-source",
+      Error::WrongColor { .. } => "wrong-color",
+      Error::NoTargets { .. } => "no-targets",
+      Error::IllegalJump { .. } => "illegal-jump",
+      Error::IllegalRemoval { .. } => "illegal-removal",
+      Error::OpeningComplete { .. } => "opening-complete",
+      Error::OpeningIncomplete { .. } => "opening-incomplete",
+      Error::TurningJump { .. } => "turning-jump",
+      Error::OffBoardJump { .. } => "off-board-jump",
+      Error::IllegalPass { .. } => "illegal-pass",
+      Error::LongJumpPath { .. } => "long-jump-path",
+      Error::GameOver { .. } => "game-over",
+      Error::BadNotation { .. } => "bad-notation",
+      Error::MisplacedPiece { .. } => "misplaced-piece",
+      Error::BadBoardSize { .. } => "bad-board-size",
+      Error::OffBoard { .. } => "off-board",
+      Error::EmptyTurn => "empty-turn",
+      Error::BadDiagram { .. } => "bad-diagram",
+      Error::BadKgn { .. } => "bad-kgn",
+      Error::Io { .. } => "io",
+      Error::NoSuchPly { .. } => "no-such-ply",
+      Error::BadTablebase { .. } => "bad-tablebase",
+    }
+  }
+
+  // The squares the error is about, in the order they appear in its fields.
+  pub fn positions(&self) -> Vec<Position> {
+    match *self {
+      Error::IllegalTarget { source_pos, target_pos, .. } => vec![source_pos, target_pos],
+      Error::IllegalJump { source_pos, mid_pos, target_pos, .. } => vec![source_pos, mid_pos, target_pos],
+      Error::TurningJump { source_pos, turn_pos, .. } => vec![source_pos, turn_pos],
+      Error::OccupiedTarget { target_pos: pos } |
+      Error::EmptySource { source_pos: pos } |
+      Error::WrongColor { source_pos: pos, .. } |
+      Error::NoTargets { source_pos: pos, .. } |
+      Error::IllegalRemoval { source_pos: pos, .. } |
+      Error::OpeningComplete { source_pos: pos } |
+      Error::OpeningIncomplete { source_pos: pos } |
+      Error::OffBoardJump { source_pos: pos, .. } |
+      Error::LongJumpPath { source_pos: pos } |
+      Error::MisplacedPiece { pos, .. } |
+      Error::OffBoard { pos, .. } => vec![pos],
+      _ => Vec::new(),
+    }
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Error::IllegalTarget { source_occ, source_pos, target_pos } => {
+        write!(fmtr,
+               "Cannot move the {} piece at {} to {}",
+               source_occ,
+               source_pos,
+               target_pos)
+      },
+      Error::OccupiedTarget { target_pos } => write!(fmtr, "The target {} is not empty", target_pos),
+      Error::EmptySource { source_pos } => write!(fmtr, "The source {} is empty", source_pos),
+      Error::WrongColor { source_occ, source_pos, desired_occ } => {
+        write!(fmtr,
+               "Cannot move the {} piece at {} during {}'s turn",
+               source_occ,
+               source_pos,
+               desired_occ)
+      },
+      Error::NoTargets { source_occ, source_pos } => {
+        write!(fmtr,
+               "No target positions were given for the {} piece at {}",
+               source_occ,
+               source_pos)
+      },
+      Error::IllegalJump { source_occ, source_pos, mid_occ, mid_pos, target_pos } => {
+        write!(fmtr,
+               "Cannot jump the {} piece at {} over the {} square {} to {}",
+               source_occ,
+               source_pos,
+               if mid_occ.is_empty() { "empty".to_owned() } else { format!("{}-occupied", mid_occ) },
+               mid_pos,
+               target_pos)
+      },
+      Error::IllegalRemoval { source_occ, source_pos } => {
+        write!(fmtr,
+               "Cannot remove the {} piece at {} during the opening",
+               source_occ,
+               source_pos)
+      },
+      Error::OpeningComplete { source_pos } => {
+        write!(fmtr,
+               "Cannot remove the piece at {} after the opening removals are complete",
+               source_pos)
+      },
+      Error::OpeningIncomplete { source_pos } => {
+        write!(fmtr,
+               "Cannot jump from {} before the opening removals are complete",
+               source_pos)
+      },
+      Error::TurningJump { source_occ, source_pos, turn_pos } => {
+        write!(fmtr,
+               "Cannot turn the {} piece from {} at {} partway through a multi-jump",
+               source_occ,
+               source_pos,
+               turn_pos)
+      },
+      Error::OffBoardJump { source_occ, source_pos, direction } => {
+        write!(fmtr,
+               "Cannot jump the {} piece at {} {} off the edge of the board",
+               source_occ,
+               source_pos,
+               direction)
+      },
+      Error::IllegalPass { player } => {
+        write!(fmtr,
+               "The {} player cannot pass: passing is only allowed without any jump to make",
+               player)
+      },
+      Error::LongJumpPath { source_pos } => {
+        write!(fmtr,
+               "Cannot record a turning multi-jump from {} of more than 64 jumps",
+               source_pos)
+      },
+      Error::GameOver { winner, plies } => {
+        write!(fmtr, "The game is already over; {} won after {} plies", winner, plies)
+      },
+      Error::BadNotation { ref text, column, expected } => {
+        write!(fmtr, "Cannot parse {:?}: expected {} at column {}", text, expected, column)
+      },
+      Error::MisplacedPiece { occ, pos } => {
+        write!(fmtr, "The {} piece at {} is on a square of the wrong colour", occ, pos)
+      },
+      Error::BadBoardSize { width, height } => {
+        write!(fmtr,
+               "Cannot make a board of {} by {} squares; each side must be between 1 and 26",
+               width,
+               height)
+      },
+      Error::OffBoard { pos, width, height } => {
+        write!(fmtr, "The position {} is off the {} by {} board", pos, width, height)
+      },
+      Error::EmptyTurn => write!(fmtr, "The side to move must be black or white"),
+      Error::BadDiagram { line, column, expected } => {
+        write!(fmtr,
+               "Cannot read the board diagram: expected {} at line {}, column {}",
+               expected,
+               line,
+               column)
+      },
+      Error::BadKgn { line, column, ref message } => {
+        write!(fmtr, "KGN error at line {}, column {}: {}", line, column, message)
+      },
+      Error::Io { ref inner } => write!(fmtr, "I/O error: {}", inner),
+      Error::NoSuchPly { ply, plies } => {
+        write!(fmtr,
+               "Cannot go to ply {} of a record holding only {} plies",
+               ply,
+               plies)
+      },
+      Error::BadTablebase { expected } => write!(fmtr, "Cannot read the tablebase: expected {}", expected),
+    }
+  }
+}
+
+impl error::Error for Error {
+  fn description(&self) -> &str {
+    match *self {
+      Error::IllegalTarget { .. } => "Cannot legally move indicated piece to target position",
+      Error::OccupiedTarget { .. } => "Target position is not empty",
+      Error::EmptySource { .. } => "Source position is empty",
+      Error::WrongColor { .. } => "Cannot legally move indicated piece during this turn",
+      Error::NoTargets { .. } => "No target positions were given",
+      Error::IllegalJump { .. } => "Cannot perform the indicated jump",
+      Error::IllegalRemoval { .. } => "Cannot remove indicated piece during the opening",
+      Error::OpeningComplete { .. } => "The opening removals are already complete",
+      Error::OpeningIncomplete { .. } => "The opening removals are not yet complete",
+      Error::TurningJump { .. } => "Cannot change direction partway through a multi-jump",
+      Error::OffBoardJump { .. } => "Cannot jump off the edge of the board",
+      Error::IllegalPass { .. } => "Cannot pass during this turn",
+      Error::LongJumpPath { .. } => "The multi-jump is too long to record",
+      Error::GameOver { .. } => "The game is already over",
+      Error::BadNotation { .. } => "Cannot parse the given notation",
+      Error::MisplacedPiece { .. } => "A piece is on a square of the wrong colour",
+      Error::BadBoardSize { .. } => "Cannot make a board of the given size",
+      Error::OffBoard { .. } => "The given position is off the board",
+      Error::EmptyTurn => "The side to move must be black or white",
+      Error::BadDiagram { .. } => "Cannot read the given board diagram",
+      Error::BadKgn { .. } => "Cannot read the given KGN game record",
+      Error::Io { .. } => "I/O error",
+      Error::NoSuchPly { .. } => "The requested ply has not been recorded",
+      Error::BadTablebase { .. } => "Cannot read the given tablebase",
+    }
+  }
+
+  fn cause(&self) -> Option<&error::Error> {
+    match *self {
+      Error::Io { ref inner } => Some(inner),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for Error {
+  fn from(inner: io::Error) -> Error { Error::Io { inner: inner } }
+}
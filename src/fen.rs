@@ -0,0 +1,194 @@
+// fen.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// A position is written as four space-separated fields, e.g. `5w/1bwbwb/... b 6x6 2`: the rows from the
+// highest rank down to rank 0 separated by '/', the side to move, the board size and the number of plies
+// played so far.  Within a row, 'b' and 'w' are pieces and a number is a run of that many empty squares.
+// A game under any rules but the standard ones adds its ruleset as a fifth field, e.g. `... 6x6 2 turning`.
+
+use std::fmt::Write;
+
+use {Game, GameState, MAX_DIMENSION, Occupancy, Papamu, Position, RuleSet, errors};
+
+fn bad_fen(text: &str, index: usize, expected: &'static str) -> errors::Error {
+  errors::Error::BadNotation { text: text.to_owned(), column: index + 1, expected: expected }
+}
+
+fn parse_number(text: &str, start: usize, expected: &'static str) -> errors::Result<(u32, usize)> {
+  let bytes = text.as_bytes();
+  let mut end = start;
+  let mut value = 0u32;
+  while end < bytes.len() && bytes[end].is_ascii_digit() {
+    value = match value.checked_mul(10).and_then(|value| value.checked_add(u32::from(bytes[end] - b'0'))) {
+      Some(value) => value,
+      None => try!(Err(bad_fen(text, start, expected))),
+    };
+    end += 1;
+  }
+  if end == start {
+    try!(Err(bad_fen(text, start, expected)))
+  }
+  Ok((value, end))
+}
+
+fn skip_spaces(text: &str, start: usize, expected: &'static str) -> errors::Result<usize> {
+  let bytes = text.as_bytes();
+  let mut end = start;
+  while end < bytes.len() && bytes[end] == b' ' {
+    end += 1;
+  }
+  if end == start || end == bytes.len() {
+    try!(Err(bad_fen(text, end, expected)))
+  }
+  Ok(end)
+}
+
+fn parse_rows(text: &str) -> errors::Result<(Vec<Vec<Occupancy>>, usize)> {
+  let bytes = text.as_bytes();
+  let mut rows = vec![Vec::new()];
+  let mut end = 0;
+  while end < bytes.len() && bytes[end] != b' ' {
+    match bytes[end] {
+      b'b' | b'B' => {
+        rows.last_mut().unwrap_or_else(|| unreachable!()).push(Occupancy::Black);
+        end += 1;
+      },
+      b'w' | b'W' => {
+        rows.last_mut().unwrap_or_else(|| unreachable!()).push(Occupancy::White);
+        end += 1;
+      },
+      b'/' => {
+        rows.push(Vec::new());
+        end += 1;
+      },
+      b'1'..=b'9' => {
+        let (run, next_end) = try!(parse_number(text, end, "a run of at most 26 empty squares"));
+        if run > u32::from(MAX_DIMENSION) {
+          try!(Err(bad_fen(text, end, "a run of at most 26 empty squares")))
+        }
+        for _ in 0..run {
+          rows.last_mut().unwrap_or_else(|| unreachable!()).push(Occupancy::Empty);
+        }
+        end = next_end;
+      },
+      _ => try!(Err(bad_fen(text, end, "'b', 'w', '/' or a run of empty squares"))),
+    }
+    if rows.len() > usize::from(MAX_DIMENSION) ||
+       rows.last().map_or(false, |row| row.len() > usize::from(MAX_DIMENSION)) {
+      try!(Err(bad_fen(text, end - 1, "at most 26 rows of at most 26 squares")))
+    }
+  }
+  Ok((rows, end))
+}
+
+impl Game {
+  pub fn to_fen(&self) -> String {
+    let papamu = self.papamu();
+    let mut fen = String::new();
+    for y in (0..papamu.height()).rev() {
+      let mut run = 0;
+      for x in 0..papamu.width() {
+        let occ = papamu[Position { x: x, y: y }];
+        if occ.is_empty() {
+          run += 1;
+          continue;
+        }
+        if run > 0 {
+          let _ = write!(fen, "{}", run);
+          run = 0;
+        }
+        fen.push(if occ.is_black() { 'b' } else { 'w' });
+      }
+      if run > 0 {
+        let _ = write!(fen, "{}", run);
+      }
+      if y > 0 {
+        fen.push('/');
+      }
+    }
+    let side = if self.current_player().is_black() { 'b' } else { 'w' };
+    let _ = write!(fen,
+                   " {} {}x{} {}",
+                   side,
+                   papamu.width(),
+                   papamu.height(),
+                   self.plies());
+    if self.rules() != RuleSet::standard() {
+      let _ = write!(fen, " {}", self.rules());
+    }
+    fen
+  }
+
+  pub fn from_fen(text: &str) -> errors::Result<Game> {
+    let bytes = text.as_bytes();
+    let (rows, rows_end) = try!(parse_rows(text));
+    let height = rows.len();
+    let width = rows[0].len();
+    if let Some(y) = rows.iter().position(|row| row.len() != width || row.is_empty()) {
+      let column = text.split('/').take(y).map(|row| row.len() + 1).sum();
+      try!(Err(bad_fen(text, column, "rows that all have the same nonzero number of squares")))
+    }
+
+    let side_start = try!(skip_spaces(text, rows_end, "the side to move"));
+    let black_to_move = match bytes[side_start] {
+      b'b' | b'B' => true,
+      b'w' | b'W' => false,
+      _ => try!(Err(bad_fen(text, side_start, "the side to move, 'b' or 'w'"))),
+    };
+
+    let size_start = try!(skip_spaces(text, side_start + 1, "the board size"));
+    let (size_width, x_end) = try!(parse_number(text, size_start, "the board width"));
+    if bytes.get(x_end) != Some(&b'x') {
+      try!(Err(bad_fen(text, x_end, "'x' between the board width and height")))
+    }
+    let (size_height, size_end) = try!(parse_number(text, x_end + 1, "the board height"));
+    if size_width != width as u32 || size_height != height as u32 {
+      try!(Err(bad_fen(text, size_start, "a board size matching the rows")))
+    }
+
+    let (plies, plies_end) = if size_end == bytes.len() {
+      (0, size_end)
+    } else {
+      let plies_start = try!(skip_spaces(text, size_end, "the number of plies"));
+      try!(parse_number(text, plies_start, "the number of plies"))
+    };
+    let rules = if plies_end == bytes.len() {
+      RuleSet::standard()
+    } else {
+      let rules_start = try!(skip_spaces(text, plies_end, "the ruleset"));
+      match text[rules_start..].parse() {
+        Ok(rules) => rules,
+        Err(_) => try!(Err(bad_fen(text, rules_start, "a ruleset such as \"corner+turning\""))),
+      }
+    };
+
+    let mut papamu = try!(Papamu::new(width as u8, height as u8)
+                            .ok_or_else(|| bad_fen(text, 0, "a board of at most 26 by 26 squares")));
+    for (row, y) in rows.iter().zip((0..height).rev()) {
+      for (x, &occ) in row.iter().enumerate() {
+        papamu[Position {
+          x: x as u8,
+          y: y as u8,
+        }] = occ;
+      }
+    }
+    try!(papamu.check_parity());
+    Ok(if black_to_move {
+      Game::Black(GameState::from_papamu(papamu, plies, rules))
+    } else {
+      Game::White(GameState::from_papamu(papamu, plies, rules))
+    })
+  }
+}
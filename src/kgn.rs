@@ -0,0 +1,502 @@
+// kgn.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// KGN is a PGN-like text format for recorded games: a block of [Name "value"] header tags followed by
+// numbered movetext and a result token (B, W or *). Moves use the notation of `Move`'s `Display`
+// implementation, comments are written {like this} or run from ';' to the end of the line, and any number
+// of games may follow one another in a single file. Tag values escape '"', '\' and control characters
+// the way Rust string literals do.
+
+use std::io::{BufRead, Write};
+use std::str;
+
+use {Game, GameRecord, RuleSet, errors};
+use notation::parse_move;
+
+const ROSTER: [&'static str; 7] = ["Black", "White", "Date", "Size", "Variant", "First", "Result"];
+
+const LINE_WIDTH: usize = 79;
+
+#[derive(Clone,PartialEq,Eq,Debug)]
+pub struct KgnGame {
+  tags: Vec<(String, String)>,
+  record: GameRecord,
+  comments: Vec<(usize, String)>,
+}
+
+impl From<GameRecord> for KgnGame {
+  fn from(record: GameRecord) -> KgnGame { KgnGame::new(record) }
+}
+
+impl KgnGame {
+  pub fn new(record: GameRecord) -> KgnGame {
+    KgnGame {
+      tags: Vec::new(),
+      record: record,
+      comments: Vec::new(),
+    }
+  }
+
+  pub fn tags(&self) -> &[(String, String)] { &self.tags }
+
+  pub fn tag(&self, name: &str) -> Option<&str> {
+    self.tags.iter().find(|&&(ref tag, _)| tag == name).map(|&(_, ref value)| &value[..])
+  }
+
+  pub fn set_tag(&mut self, name: &str, value: &str) {
+    match self.tags.iter().position(|&(ref tag, _)| tag == name) {
+      Some(index) => self.tags[index].1 = value.to_owned(),
+      None => self.tags.push((name.to_owned(), value.to_owned())),
+    }
+  }
+
+  pub fn record(&self) -> &GameRecord { &self.record }
+
+  pub fn record_mut(&mut self) -> &mut GameRecord { &mut self.record }
+
+  pub fn into_record(self) -> GameRecord { self.record }
+
+  pub fn comments(&self) -> &[(usize, String)] { &self.comments }
+
+  // What the moves give, or for an unfinished game (one resigned or adjudicated, say) the result its
+  // "Result" tag declares.
+  pub fn result(&self) -> &str {
+    match (result_token(&self.record), self.tag("Result")) {
+      ("*", Some(declared)) if declared == "B" || declared == "W" => declared,
+      (actual, _) => actual,
+    }
+  }
+
+  // The comment is written before the move of the given ply, or after the last move when `ply` is the
+  // length of the record.
+  pub fn add_comment(&mut self, ply: usize, text: &str) -> errors::Result<()> {
+    if ply > self.record.len() {
+      try!(Err(errors::Error::NoSuchPly { ply: ply, plies: self.record.len() }))
+    }
+    self.comments.push((ply, text.chars().filter(|&c| c != '}').collect()));
+    Ok(())
+  }
+
+  fn roster_value(&self, name: &str) -> String {
+    let start = self.record.start();
+    match name {
+      "Size" => format!("{}x{}", start.papamu().width(), start.papamu().height()),
+      "First" => if start.current_player().is_white() { "White" } else { "Black" }.to_owned(),
+      "Result" => self.result().to_owned(),
+      "Variant" => start.rules().to_string(),
+      _ => self.tag(name).unwrap_or("?").to_owned(),
+    }
+  }
+
+  pub fn write_to<W: Write>(&self, out: &mut W) -> errors::Result<()> {
+    for name in ROSTER.iter() {
+      try!(write_tag(out, name, &self.roster_value(name)));
+    }
+    for &(ref name, ref value) in self.tags.iter().filter(|&&(ref name, _)| !ROSTER.contains(&&name[..])) {
+      try!(write_tag(out, name, value));
+    }
+    try!(writeln!(out, ""));
+    let mut line = String::new();
+    let mut tokens = Vec::new();
+    for ply in 0..self.record.len() + 1 {
+      for &(_, ref text) in self.comments.iter().filter(|&&(comment_ply, _)| comment_ply == ply) {
+        tokens.push(format!("{{{}}}", text));
+      }
+      if let Some(mv) = self.record.moves().get(ply) {
+        if ply % 2 == 0 {
+          tokens.push(format!("{}.", ply / 2 + 1));
+        }
+        tokens.push(mv.to_string());
+      }
+    }
+    tokens.push(self.result().to_owned());
+    for token in tokens {
+      if !line.is_empty() && line.len() + 1 + token.len() > LINE_WIDTH {
+        try!(writeln!(out, "{}", line));
+        line.clear();
+      }
+      if !line.is_empty() {
+        line.push(' ');
+      }
+      line.push_str(&token);
+    }
+    try!(writeln!(out, "{}", line));
+    try!(writeln!(out, ""));
+    Ok(())
+  }
+
+  pub fn to_kgn(&self) -> String {
+    let mut out = Vec::new();
+    self.write_to(&mut out).unwrap_or_else(|_| unreachable!());
+    String::from_utf8(out).unwrap_or_else(|_| unreachable!())
+  }
+}
+
+impl str::FromStr for KgnGame {
+  type Err = errors::Error;
+
+  fn from_str(text: &str) -> errors::Result<KgnGame> {
+    match KgnReader::new(text.as_bytes()).next() {
+      Some(game) => game,
+      None => Err(errors::Error::BadKgn { line: 1, column: 1, message: "expected a game".to_owned() }),
+    }
+  }
+}
+
+fn result_token(record: &GameRecord) -> &'static str {
+  match record.position(record.len()).and_then(Game::outcome) {
+    Some(outcome) if outcome.winner.is_black() => "B",
+    Some(_) => "W",
+    None => "*",
+  }
+}
+
+fn write_tag<W: Write>(out: &mut W, name: &str, value: &str) -> errors::Result<()> {
+  let mut escaped = String::new();
+  for c in value.chars() {
+    if c == '"' || c == '\\' || c.is_control() {
+      escaped.extend(c.escape_default());
+    } else {
+      escaped.push(c);
+    }
+  }
+  try!(writeln!(out, "[{} \"{}\"]", name, escaped));
+  Ok(())
+}
+
+pub fn write_games<'a, W: Write, Gs>(out: &mut W, games: Gs) -> errors::Result<()>
+  where Gs: IntoIterator<Item = &'a KgnGame> {
+  for game in games {
+    try!(game.write_to(out));
+  }
+  Ok(())
+}
+
+pub struct KgnReader<R: BufRead> {
+  input: R,
+  line: Vec<char>,
+  line_number: usize,
+  column: usize,
+  recovering: bool,
+}
+
+impl<R: BufRead> KgnReader<R> {
+  pub fn new(input: R) -> KgnReader<R> {
+    KgnReader {
+      input: input,
+      line: Vec::new(),
+      line_number: 0,
+      column: 0,
+      recovering: false,
+    }
+  }
+
+  fn error<T>(&self, line_number: usize, column: usize, message: String) -> errors::Result<T> {
+    Err(errors::Error::BadKgn { line: line_number, column: column + 1, message: message })
+  }
+
+  fn peek(&mut self) -> errors::Result<Option<char>> {
+    while self.column >= self.line.len() {
+      let mut buffer = String::new();
+      if try!(self.input.read_line(&mut buffer)) == 0 {
+        return Ok(None);
+      }
+      self.line = buffer.chars().collect();
+      self.line_number += 1;
+      self.column = 0;
+    }
+    Ok(Some(self.line[self.column]))
+  }
+
+  fn skip_whitespace(&mut self) -> errors::Result<()> {
+    while let Some(c) = try!(self.peek()) {
+      if !c.is_whitespace() {
+        break;
+      }
+      self.column += 1;
+    }
+    Ok(())
+  }
+
+  fn read_word(&mut self) -> errors::Result<String> {
+    let mut word = String::new();
+    while let Some(c) = try!(self.peek()) {
+      if c.is_whitespace() || "[]{};".contains(c) {
+        break;
+      }
+      word.push(c);
+      self.column += 1;
+    }
+    Ok(word)
+  }
+
+  fn read_tag(&mut self) -> errors::Result<(String, String)> {
+    try!(self.skip_whitespace());
+    let (line_number, column) = (self.line_number, self.column);
+    let name = try!(self.read_word());
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+      return self.error(line_number, column, "expected a tag name".to_owned());
+    }
+    try!(self.skip_whitespace());
+    if try!(self.peek()) != Some('"') {
+      return self.error(self.line_number, self.column, "expected a quoted tag value".to_owned());
+    }
+    self.column += 1;
+    let mut value = String::new();
+    loop {
+      match try!(self.peek()) {
+        Some('"') => break,
+        Some('\\') => {
+          self.column += 1;
+          match try!(self.peek()) {
+            Some(c) if c == '"' || c == '\\' => value.push(c),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('u') => value.push(try!(self.read_unicode_escape())),
+            _ => {
+              return self.error(self.line_number,
+                                self.column,
+                                "expected an escape sequence after '\\'".to_owned())
+            },
+          }
+        },
+        Some('\n') | None => {
+          return self.error(self.line_number,
+                            self.column,
+                            "expected the closing '\"' of the tag value".to_owned())
+        },
+        Some(c) => value.push(c),
+      }
+      self.column += 1;
+    }
+    self.column += 1;
+    try!(self.skip_whitespace());
+    if try!(self.peek()) != Some(']') {
+      return self.error(self.line_number, self.column, "expected ']' after the tag value".to_owned());
+    }
+    self.column += 1;
+    Ok((name, value))
+  }
+
+  // Reads the "{XXXX}" of a "\\u{XXXX}" escape, leaving the reader on the closing brace.
+  fn read_unicode_escape(&mut self) -> errors::Result<char> {
+    let (line_number, column) = (self.line_number, self.column - 1);
+    self.column += 1;
+    if try!(self.peek()) != Some('{') {
+      return self.error(line_number, column, "expected '{' after '\\u'".to_owned());
+    }
+    let mut digits = String::new();
+    loop {
+      self.column += 1;
+      match try!(self.peek()) {
+        Some('}') => break,
+        Some(c) if c.is_digit(16) && digits.len() < 6 => digits.push(c),
+        _ => return self.error(line_number, column, "expected up to six hex digits and '}'".to_owned()),
+      }
+    }
+    match u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32) {
+      Some(c) => Ok(c),
+      None => self.error(line_number, column, format!("\\u{{{}}} is not a character", digits)),
+    }
+  }
+
+  fn read_comment(&mut self) -> errors::Result<String> {
+    let (line_number, column) = (self.line_number, self.column);
+    self.column += 1;
+    let mut text = String::new();
+    loop {
+      match try!(self.peek()) {
+        Some('}') => break,
+        Some(c) => text.push(c),
+        None => return self.error(line_number, column, "expected the closing '}' of the comment".to_owned()),
+      }
+      self.column += 1;
+    }
+    self.column += 1;
+    Ok(text.trim().to_owned())
+  }
+
+  fn start_game(&self, tags: &[(String, String)], tag_positions: &[(usize, usize)]) -> errors::Result<Game> {
+    let tag = |name: &str| {
+      tags.iter()
+          .zip(tag_positions)
+          .find(|&(&(ref tag, _), _)| tag == name)
+          .map(|(&(_, ref value), &position)| (&value[..], position))
+    };
+    let (width, height) = match tag("Size") {
+      None => (10, 10),
+      Some((size, (line_number, column))) => {
+        let mut dimensions = size.splitn(2, 'x').map(str::parse::<u8>);
+        match (dimensions.next(), dimensions.next()) {
+          (Some(Ok(width)), Some(Ok(height))) => (width, height),
+          _ => {
+            return self.error(line_number,
+                              column,
+                              format!("expected a board size like \"10x10\", not {:?}", size))
+          },
+        }
+      },
+    };
+    let rules = match tag("Variant") {
+      None => RuleSet::default(),
+      Some((variant, (line_number, column))) => {
+        match variant.parse() {
+          Ok(rules) => rules,
+          Err(_) => return self.error(line_number, column, format!("unsupported variant {:?}", variant)),
+        }
+      },
+    };
+    let game = match tag("First") {
+      None | Some(("Black", _)) => Game::new_black_sized(width, height),
+      Some(("White", _)) => Game::new_white_sized(width, height),
+      Some((first, (line_number, column))) => {
+        return self.error(line_number, column, format!("expected \"Black\" or \"White\", not {:?}", first))
+      },
+    };
+    match (game, tag("Size")) {
+      (Some(game), _) => Ok(game.with_rules(rules)),
+      (None, Some((_, (line_number, column)))) => {
+        self.error(line_number, column, format!("unsupported board size {}x{}", width, height))
+      },
+      (None, None) => unreachable!(),
+    }
+  }
+
+  fn read_game(&mut self) -> errors::Result<Option<KgnGame>> {
+    try!(self.skip_whitespace());
+    let mut tags = Vec::new();
+    let mut tag_positions = Vec::new();
+    let mut comments = Vec::new();
+    loop {
+      match try!(self.peek()) {
+        Some('[') => {
+          tag_positions.push((self.line_number, self.column));
+          self.column += 1;
+          tags.push(try!(self.read_tag()));
+        },
+        Some('{') => comments.push((0, try!(self.read_comment()))),
+        Some(';') => self.column = self.line.len(),
+        Some(_) => break,
+        None if tags.is_empty() && comments.is_empty() => return Ok(None),
+        None => break,
+      }
+      try!(self.skip_whitespace());
+    }
+    let mut game = KgnGame::new(GameRecord::new(try!(self.start_game(&tags, &tag_positions))));
+    game.tags = tags;
+    game.comments = comments;
+    let mut result = None;
+    let mut result_position = (self.line_number, self.column);
+    loop {
+      try!(self.skip_whitespace());
+      let (line_number, column) = (self.line_number, self.column);
+      match try!(self.peek()) {
+        None | Some('[') => break,
+        Some('{') => {
+          let text = try!(self.read_comment());
+          game.comments.push((game.record.len(), text));
+          continue;
+        },
+        Some(';') => {
+          self.column = self.line.len();
+          continue;
+        },
+        Some(']') | Some('}') => {
+          return self.error(line_number, column, "unexpected closing bracket".to_owned())
+        },
+        Some(_) => {},
+      }
+      let mut word = try!(self.read_word());
+      if word == "B" || word == "W" || word == "*" {
+        result = Some(word);
+        result_position = (line_number, column);
+        break;
+      }
+      let number_length = word.chars().take_while(|c| c.is_digit(10)).count();
+      if number_length > 0 && word[number_length..].starts_with('.') {
+        word = word[number_length..].trim_left_matches('.').to_owned();
+        if word.is_empty() {
+          continue;
+        }
+      }
+      if word == "x" || word == "X" {
+        try!(self.skip_whitespace());
+        word.push(' ');
+        word.push_str(&try!(self.read_word()));
+      }
+      let mv = match parse_move(&word) {
+        Ok(mv) => mv,
+        Err(err) => return self.error(line_number, column, err.to_string()),
+      };
+      if let Err(err) = game.record.push(mv) {
+        return self.error(line_number, column, err.to_string());
+      }
+    }
+    let declared = result.or_else(|| game.tag("Result").map(str::to_owned));
+    let actual = result_token(&game.record);
+    match declared {
+      Some(ref declared) if declared != "B" && declared != "W" && declared != "*" => {
+        self.error(result_position.0,
+                   result_position.1,
+                   format!("expected a result of \"B\", \"W\" or \"*\", not {:?}", declared))
+      },
+      Some(ref declared) if actual != "*" && declared != "*" && declared != actual => {
+        self.error(result_position.0,
+                   result_position.1,
+                   format!("the game is recorded as {:?} but its moves give {:?}", declared, actual))
+      },
+      Some(declared) => {
+        if actual == "*" {
+          game.set_tag("Result", &declared);
+        }
+        Ok(Some(game))
+      },
+      None => Ok(Some(game)),
+    }
+  }
+
+  fn recover(&mut self) -> errors::Result<()> {
+    loop {
+      match try!(self.peek()) {
+        Some('[') if self.column == 0 => return Ok(()),
+        None => return Ok(()),
+        Some(_) => self.column += 1,
+      }
+    }
+  }
+}
+
+impl<R: BufRead> Iterator for KgnReader<R> {
+  type Item = errors::Result<KgnGame>;
+
+  fn next(&mut self) -> Option<errors::Result<KgnGame>> {
+    if self.recovering {
+      self.recovering = false;
+      if let Err(err) = self.recover() {
+        return Some(Err(err));
+      }
+    }
+    match self.read_game() {
+      Ok(game) => game.map(Ok),
+      Err(err) => {
+        self.recovering = true;
+        Some(Err(err))
+      },
+    }
+  }
+}
+
+pub fn read_games<R: BufRead>(input: R) -> KgnReader<R> { KgnReader::new(input) }
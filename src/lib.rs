@@ -19,8 +19,11 @@ use std::marker::PhantomData;
 use std::borrow::Borrow;
 use std::{fmt, hash, ops};
 
+extern crate rand;
+
+#[cfg(feature = "serde")]
 #[macro_use]
-extern crate error_chain;
+extern crate serde;
 
 pub mod turn {
   pub enum Black {}
@@ -46,58 +49,10 @@ pub mod turn {
   }
 }
 
-pub mod errors {
-  error_chain! {
-    types {
-      Error, ErrorKind, ChainErr, Result;
-    }
-    links {}
-    foreign_links {}
-    errors {
-      IllegalTarget(source_occ: ::Occupancy,
-                    source_pos: ::Position,
-                    target_pos: ::Position) {
-        description("Cannot legally move indicated piece to target position")
-        display("Cannot move {} piece at source {} to target {}",
-                source_occ, source_pos, target_pos)
-      }
-      OcuppiedTarget(target_pos: ::Position) {
-        description("Target position is not empty")
-        display("Target {} is not empty",
-                target_pos)
-      }
-      EmptySource(source_pos: ::Position) {
-        description("Source position is empty")
-        display("Source {} is not empty",
-                source_pos)
-      }
-      WrongColor(source_occ: ::Occupancy,
-                 source_pos: ::Position,
-                 desired_occ: ::Occupancy) {
-        description("Cannot legally move indicated piece during this turn")
-        display("Cannot move {} piece at source {} during {} turn",
-                source_occ, source_pos, desired_occ)
-      }
-      NoTargets(source_occ: ::Occupancy,
-                source_pos: ::Position) {
-        description("No target positions were given")
-        display("No target positions were given for the {} piece at source {}",
-                source_occ, source_pos)
-      }
-      IllegalJump(source_occ: ::Occupancy,
-                  source_pos: ::Position,
-                  mid_occ: ::Occupancy,
-                  mid_pos: ::Position,
-                  target_pos: ::Position) {
-        description("Cannot perform the indicated jump")
-        display("Cannot jump the {} piece at {} over the currently-{} {} to {}",
-                source_occ, source_pos, mid_occ, mid_pos, target_pos)
-      }
-    }
-  }
-}
+pub mod errors;
 
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Occupancy {
   White,
   Black,
@@ -128,20 +83,306 @@ impl Occupancy {
   pub fn is_nonblack(&self) -> bool { *self != Occupancy::Black }
 }
 
+pub const MAX_DIMENSION: u8 = 26;
+
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
 pub struct Papamu {
-  board: [[Occupancy; 10]; 10],
+  width: u8,
+  height: u8,
+  board: [[Occupancy; MAX_DIMENSION as usize]; MAX_DIMENSION as usize],
 }
 
 impl Default for Papamu {
-  fn default() -> Papamu {
-    let mut board = [[Occupancy::White; 10]; 10];
-    for (_, pos) in board.iter_mut().enumerate().flat_map(|(x, part)| {
-      part.iter_mut().enumerate().filter(move |&(y, _)| (x + y) % 2 == 0)
-    }) {
-      *pos = Occupancy::Black;
+  fn default() -> Papamu { Papamu::new(10, 10).unwrap_or_else(|| unreachable!()) }
+}
+
+impl Papamu {
+  pub fn new(width: u8, height: u8) -> Option<Papamu> {
+    if width == 0 || height == 0 || width > MAX_DIMENSION || height > MAX_DIMENSION {
+      return None;
+    }
+    let mut board = [[Occupancy::Empty; MAX_DIMENSION as usize]; MAX_DIMENSION as usize];
+    for (x, part) in board.iter_mut().enumerate().take(usize::from(width)) {
+      for (y, pos) in part.iter_mut().enumerate().take(usize::from(height)) {
+        *pos = if (x + y) % 2 == 0 { Occupancy::Black } else { Occupancy::White };
+      }
+    }
+    Some(Papamu {
+      width: width,
+      height: height,
+      board: board,
+    })
+  }
+
+  pub fn width(&self) -> u8 { self.width }
+
+  pub fn height(&self) -> u8 { self.height }
+
+  pub fn contains(&self, pos: Position) -> bool { pos.x < self.width && pos.y < self.height }
+
+  pub fn bitboard(&self) -> Option<Bitboard> { Bitboard::new(self) }
+
+  pub fn position(&self, x: u8, y: u8) -> Option<Position> {
+    Position::new(x, y).and_then(|pos| if self.contains(pos) { Some(pos) } else { None })
+  }
+
+  pub fn offset(&self, pos: Position, direction: Direction, distance: u8) -> Option<Position> {
+    pos.offset(direction, distance).and_then(|pos| if self.contains(pos) { Some(pos) } else { None })
+  }
+
+  pub fn square_colour(&self, pos: Position) -> Occupancy {
+    if (pos.x + pos.y) % 2 == 0 { Occupancy::Black } else { Occupancy::White }
+  }
+
+  pub fn check_parity(&self) -> errors::Result<()> {
+    for pos in self.positions() {
+      if self[pos].is_occupied() && self[pos] != self.square_colour(pos) {
+        try!(Err(errors::Error::MisplacedPiece { occ: self[pos], pos: pos }))
+      }
+    }
+    Ok(())
+  }
+
+  pub fn is_corner(&self, pos: Position) -> bool {
+    self.contains(pos) && (pos.x == 0 || pos.x == self.width - 1) && (pos.y == 0 || pos.y == self.height - 1)
+  }
+
+  pub fn is_centre(&self, pos: Position) -> bool {
+    self.contains(pos) && (pos.x == (self.width - 1) / 2 || pos.x == self.width / 2) &&
+    (pos.y == (self.height - 1) / 2 || pos.y == self.height / 2)
+  }
+
+  fn positions(&self) -> Positions {
+    Positions {
+      width: self.width,
+      height: self.height,
+      next: Some(Position { x: 0, y: 0 }),
     }
-    Papamu { board: board }
+  }
+
+  // Squares off the board read as empty, so a move from one fails the way a move from an empty square does.
+  fn occupancy(&self, pos: Position) -> Occupancy {
+    if self.contains(pos) { self[pos] } else { Occupancy::Empty }
+  }
+
+  fn empty_count(&self) -> usize { self.positions().filter(|&pos| self[pos].is_empty()).count() }
+
+  pub fn is_opening(&self) -> bool { self.empty_count() < 2 }
+
+  fn check_removal(&self, player: Occupancy, target: Position, opening: OpeningRule) -> errors::Result<()> {
+    if self.occupancy(target).is_empty() {
+      try!(Err(errors::Error::EmptySource { source_pos: target }))
+    }
+    if self[target] != player {
+      try!(Err(errors::Error::WrongColor {
+        source_occ: self[target],
+        source_pos: target,
+        desired_occ: player,
+      }))
+    }
+    match (self.empty_count(), self.positions().find(|&pos| self[pos].is_empty())) {
+      (0, _) if !opening.allows(self, target) => {
+        try!(Err(errors::Error::IllegalRemoval { source_occ: self[target], source_pos: target }))
+      },
+      (1, Some(removed)) if !removed.is_adjacent(target) => {
+        try!(Err(errors::Error::IllegalRemoval { source_occ: self[target], source_pos: target }))
+      },
+      (0, _) | (1, _) => {},
+      _ => try!(Err(errors::Error::OpeningComplete { source_pos: target })),
+    }
+    Ok(())
+  }
+
+  fn can_jump(&self, player: Occupancy, source: Position, direction: Direction, jumps: u8) -> bool {
+    let mid = u8::checked_mul(jumps, 2).and_then(|distance| self.offset(source, direction, distance - 1));
+    let target = u8::checked_mul(jumps, 2).and_then(|distance| self.offset(source, direction, distance));
+    match (mid, target) {
+      (Some(mid), Some(target)) => {
+        self[source] == player && self[mid].is_occupied() && self[mid] != player && self[target].is_empty()
+      },
+      _ => false,
+    }
+  }
+
+  fn jump_chains(&self, player: Occupancy, source: Position) -> Vec<Move> {
+    let mut chains = Vec::new();
+    let mut scratch = *self;
+    scratch.extend_chains(player, source, source, &mut JumpPath::new(), &mut chains);
+    chains
+  }
+
+  fn extend_chains(&mut self,
+                   player: Occupancy,
+                   source: Position,
+                   current: Position,
+                   path: &mut JumpPath,
+                   chains: &mut Vec<Move>) {
+    for &direction in &DIRECTIONS {
+      if path.len() == MAX_PATH_JUMPS || !self.can_jump(player, current, direction, 1) {
+        continue;
+      }
+      let mid = current.offset(direction, 1).unwrap_or_else(|| unreachable!());
+      let target = current.offset(direction, 2).unwrap_or_else(|| unreachable!());
+      let captured = self[mid];
+      self[current] = Occupancy::Empty;
+      self[mid] = Occupancy::Empty;
+      self[target] = player;
+      path.push(direction);
+      chains.push(Move::path(source, *path));
+      self.extend_chains(player, source, target, path, chains);
+      path.pop();
+      self[target] = Occupancy::Empty;
+      self[mid] = captured;
+      self[current] = player;
+    }
+  }
+}
+
+struct Positions {
+  width: u8,
+  height: u8,
+  next: Option<Position>,
+}
+
+impl Iterator for Positions {
+  type Item = Position;
+
+  fn next(&mut self) -> Option<Position> {
+    let current = self.next;
+    self.next = current.and_then(|pos| {
+      if pos.y + 1 < self.height {
+        Some(Position { x: pos.x, y: pos.y + 1 })
+      } else if pos.x + 1 < self.width {
+        Some(Position { x: pos.x + 1, y: 0 })
+      } else {
+        None
+      }
+    });
+    current
+  }
+}
+
+pub struct LegalMoves<'a> {
+  papamu: &'a Papamu,
+  bitboard: Option<Bitboard>,
+  movable: u128,
+  player: Occupancy,
+  rules: RuleSet,
+  opening: bool,
+  sources: Positions,
+  source: Option<Position>,
+  direction: usize,
+  jumps: u8,
+  chains: Vec<Move>,
+  found: bool,
+  passed: bool,
+}
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+impl<'a> LegalMoves<'a> {
+  fn new(papamu: &'a Papamu, player: Occupancy, rules: RuleSet) -> LegalMoves<'a> {
+    let opening = papamu.is_opening();
+    let bitboard = if opening { None } else { papamu.bitboard() };
+    let mut moves = LegalMoves {
+      papamu: papamu,
+      bitboard: bitboard,
+      movable: bitboard.map_or(0, |bitboard| bitboard.movable(player)),
+      player: player,
+      rules: rules,
+      opening: opening,
+      sources: papamu.positions(),
+      source: None,
+      direction: 0,
+      jumps: 0,
+      chains: Vec::new(),
+      found: false,
+      passed: false,
+    };
+    moves.advance();
+    moves
+  }
+
+  fn advance(&mut self) {
+    self.source = self.next_source();
+    self.direction = 0;
+    self.jumps = 0;
+    if let (Some(source), true, false) = (self.source, self.rules.turning_jumps, self.opening) {
+      self.chains = self.papamu.jump_chains(self.player, source);
+      self.chains.reverse();
+    }
+  }
+
+  fn opponent_can_jump(&self) -> bool {
+    let opponent = if self.player.is_black() { Occupancy::White } else { Occupancy::Black };
+    LegalMoves::new(self.papamu, opponent, RuleSet { passing: false, ..self.rules }).next().is_some()
+  }
+
+  fn next_jump_or_removal(&mut self) -> Option<Move> {
+    while let Some(source) = self.source {
+      if self.opening {
+        self.advance();
+        if self.papamu.check_removal(self.player, source, self.rules.opening).is_ok() {
+          return Some(Move::Remove(source));
+        }
+      } else if self.rules.turning_jumps {
+        match self.chains.pop() {
+          Some(mv) => return Some(mv),
+          None => self.advance(),
+        }
+      } else if self.papamu[source] == self.player && self.direction < DIRECTIONS.len() {
+        let direction = DIRECTIONS[self.direction];
+        if self.can_jump(source, direction, self.jumps + 1) {
+          self.jumps += 1;
+          return Some(Move::jump(source, direction, self.jumps));
+        }
+        self.direction += 1;
+        self.jumps = 0;
+      } else {
+        self.advance();
+      }
+    }
+    None
+  }
+
+  fn next_source(&mut self) -> Option<Position> {
+    match self.bitboard {
+      Some(bitboard) => {
+        if self.movable == 0 {
+          None
+        } else {
+          let index = self.movable.trailing_zeros();
+          self.movable &= self.movable - 1;
+          bitboard.position(index)
+        }
+      },
+      None => self.sources.next(),
+    }
+  }
+
+  fn can_jump(&self, source: Position, direction: Direction, jumps: u8) -> bool {
+    match self.bitboard {
+      Some(bitboard) => bitboard.can_jump(self.player, source, direction, jumps),
+      None => self.papamu.can_jump(self.player, source, direction, jumps),
+    }
+  }
+}
+
+impl<'a> Iterator for LegalMoves<'a> {
+  type Item = Move;
+
+  fn next(&mut self) -> Option<Move> {
+    if let Some(mv) = self.next_jump_or_removal() {
+      self.found = true;
+      return Some(mv);
+    }
+    if self.rules.passing && !self.opening && !self.found && !self.passed {
+      self.passed = true;
+      if self.opponent_can_jump() {
+        return Some(Move::Pass);
+      }
+    }
+    None
   }
 }
 
@@ -151,23 +392,16 @@ pub struct Position {
   y: u8,
 }
 
-impl fmt::Display for Position {
-  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
-    let (x, y) = <(char, u8)>::from(*self);
-    write!(fmtr, "position {}{}", x, y)
-  }
-}
-
 impl Position {
   pub fn new(x: u8, y: u8) -> Option<Position> {
-    if x <= 9 && y <= 9 { Some(Position { x: x, y: y }) } else { None }
+    if x < MAX_DIMENSION && y < MAX_DIMENSION { Some(Position { x: x, y: y }) } else { None }
   }
 
   pub fn x(&self) -> u8 { self.x }
 
   pub fn set_x(&mut self, new_x: u8) -> Option<u8> {
-    if new_x <= 9 {
-      let old_x = self.y;
+    if new_x < MAX_DIMENSION {
+      let old_x = self.x;
       self.x = new_x;
       Some(old_x)
     } else {
@@ -178,7 +412,7 @@ impl Position {
   pub fn y(&self) -> u8 { self.y }
 
   pub fn set_y(&mut self, new_y: u8) -> Option<u8> {
-    if new_y <= 9 {
+    if new_y < MAX_DIMENSION {
       let old_y = self.y;
       self.y = new_y;
       Some(old_y)
@@ -199,6 +433,270 @@ impl Position {
       None
     }
   }
+
+  pub fn is_adjacent(&self, other: Position) -> bool {
+    fn one_apart(a: u8, b: u8) -> bool { (a < b && b - a == 1) || (b < a && a - b == 1) }
+    (self.y == other.y && one_apart(self.x, other.x)) || (self.x == other.x && one_apart(self.y, other.y))
+  }
+}
+
+impl Position {
+  pub fn offset(&self, direction: Direction, distance: u8) -> Option<Position> {
+    match direction {
+      Direction::Up => self.y.checked_add(distance).and_then(|y| Position::new(self.x, y)),
+      Direction::Down => self.y.checked_sub(distance).and_then(|y| Position::new(self.x, y)),
+      Direction::Left => self.x.checked_sub(distance).and_then(|x| Position::new(x, self.y)),
+      Direction::Right => self.x.checked_add(distance).and_then(|x| Position::new(x, self.y)),
+    }
+  }
+
+  pub fn direction_to(&self, other: Position) -> Option<Direction> {
+    if self.x == other.x && self.y < other.y {
+      Some(Direction::Up)
+    } else if self.x == other.x && self.y > other.y {
+      Some(Direction::Down)
+    } else if self.y == other.y && self.x > other.x {
+      Some(Direction::Left)
+    } else if self.y == other.y && self.x < other.x {
+      Some(Direction::Right)
+    } else {
+      None
+    }
+  }
+}
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+  Up,
+  Down,
+  Left,
+  Right,
+}
+
+impl fmt::Display for Direction {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Direction::Up => write!(fmtr, "up"),
+      Direction::Down => write!(fmtr, "down"),
+      Direction::Left => write!(fmtr, "left"),
+      Direction::Right => write!(fmtr, "right"),
+    }
+  }
+}
+
+impl Direction {
+  pub fn reverse(&self) -> Direction {
+    match *self {
+      Direction::Up => Direction::Down,
+      Direction::Down => Direction::Up,
+      Direction::Left => Direction::Right,
+      Direction::Right => Direction::Left,
+    }
+  }
+
+  fn index(&self) -> u8 {
+    match *self {
+      Direction::Up => 0,
+      Direction::Down => 1,
+      Direction::Left => 2,
+      Direction::Right => 3,
+    }
+  }
+}
+
+pub const MAX_PATH_JUMPS: usize = 64;
+
+// The directions of a turning multi-jump, two bits apiece in the order they are taken.
+#[derive(Clone,Copy,PartialEq,Eq,Hash,Default)]
+pub struct JumpPath {
+  len: u8,
+  directions: u128,
+}
+
+impl JumpPath {
+  pub fn new() -> JumpPath { JumpPath::default() }
+
+  pub fn from_directions(directions: &[Direction]) -> Option<JumpPath> {
+    let mut path = JumpPath::new();
+    for &direction in directions {
+      if !path.push(direction) {
+        return None;
+      }
+    }
+    Some(path)
+  }
+
+  pub fn len(&self) -> usize { usize::from(self.len) }
+
+  pub fn is_empty(&self) -> bool { self.len == 0 }
+
+  pub fn get(&self, index: usize) -> Option<Direction> {
+    if index < self.len() {
+      Some(DIRECTIONS[((self.directions >> (2 * index)) & 3) as usize])
+    } else {
+      None
+    }
+  }
+
+  pub fn push(&mut self, direction: Direction) -> bool {
+    if self.len() == MAX_PATH_JUMPS {
+      return false;
+    }
+    self.directions |= u128::from(direction.index()) << (2 * self.len());
+    self.len += 1;
+    true
+  }
+
+  pub fn pop(&mut self) -> Option<Direction> {
+    let last = self.len().checked_sub(1).and_then(|index| self.get(index));
+    if last.is_some() {
+      self.len -= 1;
+      self.directions &= !(3 << (2 * self.len()));
+    }
+    last
+  }
+
+  pub fn iter(&self) -> JumpPathIter {
+    JumpPathIter {
+      path: *self,
+      straight: None,
+      index: 0,
+      len: self.len(),
+    }
+  }
+
+  pub fn is_straight(&self) -> bool { self.iter().all(|direction| Some(direction) == self.get(0)) }
+}
+
+impl fmt::Debug for JumpPath {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result { fmtr.debug_list().entries(self.iter()).finish() }
+}
+
+#[derive(Clone,Debug)]
+pub struct JumpPathIter {
+  path: JumpPath,
+  straight: Option<Direction>,
+  index: usize,
+  len: usize,
+}
+
+impl Iterator for JumpPathIter {
+  type Item = Direction;
+
+  fn next(&mut self) -> Option<Direction> {
+    if self.index == self.len {
+      return None;
+    }
+    self.index += 1;
+    self.straight.or_else(|| self.path.get(self.index - 1))
+  }
+}
+
+impl<'a> IntoIterator for &'a JumpPath {
+  type Item = Direction;
+  type IntoIter = JumpPathIter;
+
+  fn into_iter(self) -> JumpPathIter { self.iter() }
+}
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+pub enum Move {
+  Remove(Position),
+  Jump {
+    source: Position,
+    direction: Direction,
+    jumps: u8,
+  },
+  Path {
+    source: Position,
+    path: JumpPath,
+  },
+  Pass,
+}
+
+impl Move {
+  pub fn remove(target: Position) -> Move { Move::Remove(target) }
+
+  pub fn jump(source: Position, direction: Direction, jumps: u8) -> Move {
+    Move::Jump {
+      source: source,
+      direction: direction,
+      jumps: jumps,
+    }
+  }
+
+  // A path that never turns is always represented as a plain `Jump`.
+  pub fn path(source: Position, path: JumpPath) -> Move {
+    match path.get(0) {
+      Some(direction) if path.is_straight() => Move::jump(source, direction, path.len() as u8),
+      _ => {
+        Move::Path {
+          source: source,
+          path: path,
+        }
+      },
+    }
+  }
+
+  pub fn source(&self) -> Option<Position> {
+    match *self {
+      Move::Remove(target) => Some(target),
+      Move::Jump { source, .. } |
+      Move::Path { source, .. } => Some(source),
+      Move::Pass => None,
+    }
+  }
+
+  pub fn is_removal(&self) -> bool {
+    match *self {
+      Move::Remove(_) => true,
+      _ => false,
+    }
+  }
+
+  pub fn is_jump(&self) -> bool {
+    match *self {
+      Move::Jump { .. } |
+      Move::Path { .. } => true,
+      _ => false,
+    }
+  }
+
+  pub fn is_pass(&self) -> bool { *self == Move::Pass }
+
+  pub fn jumps(&self) -> usize {
+    match *self {
+      Move::Jump { jumps, .. } => usize::from(jumps),
+      Move::Path { path, .. } => path.len(),
+      _ => 0,
+    }
+  }
+
+  pub fn directions(&self) -> JumpPathIter {
+    match *self {
+      Move::Jump { direction, jumps, .. } => {
+        JumpPathIter {
+          path: JumpPath::new(),
+          straight: Some(direction),
+          index: 0,
+          len: usize::from(jumps),
+        }
+      },
+      Move::Path { path, .. } => path.iter(),
+      _ => JumpPath::new().iter(),
+    }
+  }
+
+  pub fn destination(&self) -> Option<Position> {
+    match self.source() {
+      Some(source) if self.is_jump() => {
+        self.directions()
+            .fold(Some(source), |current, direction| current.and_then(|pos| pos.offset(direction, 2)))
+      },
+      _ => None,
+    }
+  }
 }
 
 impl From<Position> for (u8, u8) {
@@ -220,33 +718,48 @@ impl From<Position> for (char, char) {
 impl ops::Index<Position> for Papamu {
   type Output = Occupancy;
 
-  fn index(&self, ix: Position) -> &Occupancy { &self.board[usize::from(ix.x)][usize::from(ix.y)] }
+  fn index(&self, ix: Position) -> &Occupancy {
+    assert!(self.contains(ix), "{} is off the {}x{} board", ix, self.width, self.height);
+    &self.board[usize::from(ix.x)][usize::from(ix.y)]
+  }
 }
 
 impl ops::IndexMut<Position> for Papamu {
   fn index_mut(&mut self, ix: Position) -> &mut Occupancy {
+    assert!(self.contains(ix), "{} is off the {}x{} board", ix, self.width, self.height);
     &mut self.board[usize::from(ix.x)][usize::from(ix.y)]
   }
 }
 
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Outcome {
+  pub winner: Occupancy,
+  pub plies: u32,
+}
+
 pub struct GameState<Tn: turn::Turn> {
   papamu: Papamu,
+  zobrist: u64,
+  plies: u32,
+  rules: RuleSet,
   phantom_turn: PhantomData<Tn>,
 }
 
 impl<Tn: turn::Turn> fmt::Debug for GameState<Tn> {
   fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
-    fmtr.debug_struct("Game").field("papamu", &self.papamu).field("phantom_turn", &self.phantom_turn).finish()
+    fmtr.debug_struct("Game")
+        .field("papamu", &self.papamu)
+        .field("zobrist", &self.zobrist)
+        .field("plies", &self.plies)
+        .field("rules", &self.rules)
+        .field("phantom_turn", &self.phantom_turn)
+        .finish()
   }
 }
 
 impl<Tn: turn::Turn> Default for GameState<Tn> {
-  fn default() -> GameState<Tn> {
-    GameState {
-      papamu: Papamu::default(),
-      phantom_turn: PhantomData,
-    }
-  }
+  fn default() -> GameState<Tn> { GameState::from_papamu(Papamu::default(), 0, RuleSet::default()) }
 }
 
 #[allow(unknown_lints, expl_impl_clone_on_copy)]
@@ -255,9 +768,9 @@ impl<Tn: turn::Turn> Clone for GameState<Tn> {
 }
 
 impl<Tn: turn::Turn> PartialEq for GameState<Tn> {
-  fn eq(&self, other: &GameState<Tn>) -> bool { self.papamu == other.papamu }
+  fn eq(&self, other: &GameState<Tn>) -> bool { self.papamu == other.papamu && self.rules == other.rules }
 
-  fn ne(&self, other: &GameState<Tn>) -> bool { self.papamu != other.papamu }
+  fn ne(&self, other: &GameState<Tn>) -> bool { self.papamu != other.papamu || self.rules != other.rules }
 }
 
 impl<Tn: turn::Turn> Eq for GameState<Tn> {}
@@ -265,10 +778,7 @@ impl<Tn: turn::Turn> Eq for GameState<Tn> {}
 impl<Tn: turn::Turn> Copy for GameState<Tn> {}
 
 impl<Tn: turn::Turn> hash::Hash for GameState<Tn> {
-  fn hash<H: hash::Hasher>(&self, hshr: &mut H) {
-    self.papamu.hash(hshr);
-    self.phantom_turn.hash(hshr);
-  }
+  fn hash<H: hash::Hasher>(&self, hshr: &mut H) { hshr.write_u64(self.zobrist) }
 }
 
 impl<Tn: turn::Turn> ops::Index<Position> for GameState<Tn> {
@@ -278,96 +788,206 @@ impl<Tn: turn::Turn> ops::Index<Position> for GameState<Tn> {
 }
 
 impl<Tn: turn::Turn> GameState<Tn> {
+  pub fn new(width: u8, height: u8) -> Option<GameState<Tn>> {
+    Papamu::new(width, height).map(|papamu| GameState::from_papamu(papamu, 0, RuleSet::default()))
+  }
+
+  fn from_papamu(papamu: Papamu, plies: u32, rules: RuleSet) -> GameState<Tn> {
+    let side_key = if Tn::piece_type().is_black() { zobrist::black_to_move_key() } else { 0 };
+    GameState {
+      zobrist: zobrist::papamu_key(&papamu) ^ side_key,
+      papamu: papamu,
+      plies: plies,
+      rules: rules,
+      phantom_turn: PhantomData,
+    }
+  }
+
+  pub fn with_rules(self, rules: RuleSet) -> GameState<Tn> { GameState { rules: rules, ..self } }
+
+  pub fn rules(&self) -> RuleSet { self.rules }
+
   pub fn papamu(&self) -> &Papamu { &self.papamu }
 
+  pub fn zobrist(&self) -> u64 { self.zobrist }
+
+  pub fn plies(&self) -> u32 { self.plies }
+
+  pub fn is_opening(&self) -> bool { self.papamu.is_opening() }
+
+  fn next_removal(&self, target: Position) -> errors::Result<GameState<Tn::Next>> {
+    try!(self.papamu.check_removal(Tn::piece_type(), target, self.rules.opening));
+    let mut papamu = self.papamu;
+    papamu[target] = Occupancy::Empty;
+    Ok(GameState {
+      papamu: papamu,
+      zobrist: self.zobrist ^ zobrist::piece_key(Tn::piece_type(), target) ^ zobrist::black_to_move_key(),
+      plies: self.plies + 1,
+      rules: self.rules,
+      phantom_turn: PhantomData,
+    })
+  }
+
+  fn next_pass(&self) -> errors::Result<GameState<Tn::Next>> {
+    if !self.legal_moves().any(|mv| mv.is_pass()) {
+      try!(Err(errors::Error::IllegalPass { player: Tn::piece_type() }))
+    }
+    Ok(GameState {
+      papamu: self.papamu,
+      zobrist: self.zobrist ^ zobrist::black_to_move_key(),
+      plies: self.plies + 1,
+      rules: self.rules,
+      phantom_turn: PhantomData,
+    })
+  }
+
+  pub fn remove_piece(&self, target: Position) -> errors::Result<GameState<Tn::Next>> {
+    self.next_turn(Move::Remove(target))
+  }
+
   fn next_subturn(&mut self, current: Position, target: Position) -> errors::Result<()> {
     if self[target].is_occupied() {
-      try!(Err(errors::ErrorKind::OcuppiedTarget(target)))
+      try!(Err(errors::Error::OccupiedTarget { target_pos: target }))
     }
     if let Some(mid) = current.biadjacency(target) {
       if self[mid] != <Tn::Next as turn::Turn>::piece_type() {
-        try!(Err(errors::ErrorKind::IllegalJump(self[current], current, self[mid], mid, target)))
+        try!(Err(errors::Error::IllegalJump {
+          source_occ: self[current],
+          source_pos: current,
+          mid_occ: self[mid],
+          mid_pos: mid,
+          target_pos: target,
+        }))
       }
       self.papamu[current] = Occupancy::Empty;
       self.papamu[mid] = Occupancy::Empty;
       self.papamu[target] = Tn::piece_type();
+      self.zobrist ^= zobrist::piece_key(Tn::piece_type(), current) ^
+                      zobrist::piece_key(<Tn::Next as turn::Turn>::piece_type(), mid) ^
+                      zobrist::piece_key(Tn::piece_type(), target);
     } else {
-      try!(Err(errors::ErrorKind::IllegalTarget(self[current], current, target)))
+      try!(Err(errors::Error::IllegalTarget {
+        source_occ: self[current],
+        source_pos: current,
+        target_pos: target,
+      }))
     }
     Ok(())
   }
 
-  pub fn next_turn<Ts: IntoIterator>(&self,
-                                     source: Position,
-                                     targets: Ts)
-                                     -> errors::Result<GameState<Tn::Next>>
+  pub fn jump_chain<Ts: IntoIterator>(&self, source: Position, targets: Ts) -> errors::Result<Move>
     where Ts::Item: Borrow<Position> {
+    let mut current = source;
+    let mut path = JumpPath::new();
+    for target_ref in targets {
+      let target = *target_ref.borrow();
+      let direction = match (current.biadjacency(target), current.direction_to(target)) {
+        (Some(_), Some(direction)) => direction,
+        _ => try!(Err(errors::Error::IllegalTarget {
+          source_occ: self.papamu.occupancy(source),
+          source_pos: current,
+          target_pos: target,
+        })),
+      };
+      let turning = path.get(0).map_or(false, |chain_direction| chain_direction != direction);
+      if turning && !self.rules.turning_jumps {
+        try!(Err(errors::Error::TurningJump {
+          source_occ: self.papamu.occupancy(source),
+          source_pos: source,
+          turn_pos: current,
+        }))
+      }
+      if !path.push(direction) {
+        try!(Err(errors::Error::LongJumpPath { source_pos: source }))
+      }
+      current = target;
+    }
+    if path.is_empty() {
+      try!(Err(errors::Error::NoTargets { source_occ: self.papamu.occupancy(source), source_pos: source }))
+    }
+    Ok(Move::path(source, path))
+  }
+
+  pub fn next_turn(&self, mv: Move) -> errors::Result<GameState<Tn::Next>> {
+    self.next_move(mv).map_err(|err| {
+      match self.outcome() {
+        Some(outcome) => errors::Error::GameOver { winner: outcome.winner, plies: outcome.plies },
+        None => err,
+      }
+    })
+  }
+
+  fn next_move(&self, mv: Move) -> errors::Result<GameState<Tn::Next>> {
+    let source = match mv {
+      Move::Remove(target) => return self.next_removal(target),
+      Move::Pass => return self.next_pass(),
+      Move::Jump { source, .. } |
+      Move::Path { source, .. } => source,
+    };
     let mut game = *self;
-    if game[source].is_empty() {
-      try!(Err(errors::ErrorKind::EmptySource(source)))
+    if game.is_opening() {
+      try!(Err(errors::Error::OpeningIncomplete { source_pos: source }))
+    }
+    if game.papamu.occupancy(source).is_empty() {
+      try!(Err(errors::Error::EmptySource { source_pos: source }))
     }
     if game[source] != Tn::piece_type() {
-      try!(Err(errors::ErrorKind::WrongColor(game[source], source, Tn::piece_type())))
+      try!(Err(errors::Error::WrongColor {
+        source_occ: game[source],
+        source_pos: source,
+        desired_occ: Tn::piece_type(),
+      }))
+    }
+    if mv.jumps() == 0 {
+      try!(Err(errors::Error::NoTargets { source_occ: self[source], source_pos: source }))
     }
     let mut current = source;
-    let mut target: Position;
-    let mut targets_empty = true;
-    for target_ref in targets {
-      targets_empty = false;
-      target = *target_ref.borrow();
+    let mut previous = None;
+    for direction in mv.directions() {
+      if !self.rules.turning_jumps && previous.map_or(false, |previous| previous != direction) {
+        try!(Err(errors::Error::TurningJump {
+          source_occ: self[source],
+          source_pos: source,
+          turn_pos: current,
+        }))
+      }
+      previous = Some(direction);
+      let target = match game.papamu.offset(current, direction, 2) {
+        Some(target) => target,
+        None => try!(Err(errors::Error::OffBoardJump {
+          source_occ: self[source],
+          source_pos: current,
+          direction: direction,
+        })),
+      };
       try!(game.next_subturn(current, target));
       current = target;
     }
-    if targets_empty {
-      try!(Err(errors::ErrorKind::NoTargets(self[source], source)))
-    }
     Ok(GameState {
       papamu: game.papamu,
+      zobrist: game.zobrist ^ zobrist::black_to_move_key(),
+      plies: self.plies + 1,
+      rules: self.rules,
       phantom_turn: PhantomData,
     })
   }
 
-  pub fn can_move(&self) -> bool {
-    fn gen_adj(x0: u8, y0: u8) -> Vec<((u8, u8), (u8, u8))> {
-      let mut x1_vec = Vec::<u8>::new();
-      let mut y1_vec = Vec::<u8>::new();
-      let mut x2_vec = Vec::<u8>::new();
-      let mut y2_vec = Vec::<u8>::new();
-      if x0 > 1 {
-        x1_vec.push(x0 - 1);
-        x2_vec.push(x0 - 2);
-      }
-      if x0 < 9 {
-        x1_vec.push(x0 + 1);
-        x2_vec.push(x0 + 2);
-      }
-      if y0 > 1 {
-        y1_vec.push(y0 - 1);
-        y2_vec.push(y0 - 2);
-      }
-      if y0 < 9 {
-        y1_vec.push(y0 + 1);
-        y2_vec.push(y0 + 2);
-      }
-      x1_vec.into_iter()
-            .map(|x1| (x1, y0))
-            .chain(y1_vec.into_iter().map(|y1| (x0, y1)))
-            .zip(x2_vec.into_iter().map(|x2| (x2, y0)).chain(y2_vec.into_iter().map(|y2| (x0, y2))))
-            .collect()
-    }
-    for x0 in 0..10u8 {
-      for y0 in 0..10u8 {
-        for ((x1, y1), (x2, y2)) in gen_adj(x0, y0) {
-          if self[Position::new(x2, y2).unwrap_or_else(|| unreachable!())].is_empty() &&
-             self[Position::new(x1, y1).unwrap_or_else(|| unreachable!())] ==
-             <Tn::Next as turn::Turn>::piece_type() {
-            return true;
-          }
-        }
-      }
+  pub fn legal_moves(&self) -> LegalMoves { LegalMoves::new(&self.papamu, Tn::piece_type(), self.rules) }
+
+  pub fn can_move(&self) -> bool { self.legal_moves().next().is_some() }
+
+  pub fn outcome(&self) -> Option<Outcome> {
+    if self.can_move() {
+      None
+    } else {
+      Some(Outcome {
+        winner: if self.rules.misere { Tn::piece_type() } else { <Tn::Next as turn::Turn>::piece_type() },
+        plies: self.plies,
+      })
     }
-    false
   }
+
+  pub fn is_over(&self) -> bool { !self.can_move() }
 }
 
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
@@ -446,6 +1066,14 @@ impl Game {
 
   pub fn new_black() -> Game { Game::Black(GameState::default()) }
 
+  pub fn new_white_sized(width: u8, height: u8) -> Option<Game> {
+    GameState::new(width, height).map(Game::White)
+  }
+
+  pub fn new_black_sized(width: u8, height: u8) -> Option<Game> {
+    GameState::new(width, height).map(Game::Black)
+  }
+
   pub fn to_white(self) -> Option<GameState<turn::White>> { self.by_color(Some, |_| None) }
 
   pub fn to_black(self) -> Option<GameState<turn::Black>> { self.by_color(|_| None, Some) }
@@ -460,18 +1088,85 @@ impl Game {
 
   pub fn papamu(&self) -> &Papamu { self.by_color_ref(GameState::papamu, GameState::papamu) }
 
-  pub fn next_turn<Ts: IntoIterator>(&mut self, source: Position, targets: Ts) -> errors::Result<()>
+  pub fn jump_chain<Ts: IntoIterator>(&self, source: Position, targets: Ts) -> errors::Result<Move>
     where Ts::Item: Borrow<Position> {
+    match *self {
+      Game::White(ref game) => game.jump_chain(source, targets),
+      Game::Black(ref game) => game.jump_chain(source, targets),
+    }
+  }
+
+  pub fn next_turn(&mut self, mv: Move) -> errors::Result<()> {
     *self = match *self {
-      Game::White(game) => Game::Black(try!(game.next_turn(source, targets))),
-      Game::Black(game) => Game::White(try!(game.next_turn(source, targets))),
+      Game::White(game) => Game::Black(try!(game.next_turn(mv))),
+      Game::Black(game) => Game::White(try!(game.next_turn(mv))),
     };
     Ok(())
   }
 
+  pub fn remove_piece(&mut self, target: Position) -> errors::Result<()> {
+    *self = match *self {
+      Game::White(game) => Game::Black(try!(game.remove_piece(target))),
+      Game::Black(game) => Game::White(try!(game.remove_piece(target))),
+    };
+    Ok(())
+  }
+
+  pub fn is_opening(&self) -> bool { self.by_color_ref(GameState::is_opening, GameState::is_opening) }
+
+  pub fn legal_moves(&self) -> LegalMoves {
+    self.by_color_ref(GameState::legal_moves, GameState::legal_moves)
+  }
+
   pub fn can_move(&self) -> bool { self.by_color_ref(GameState::can_move, GameState::can_move) }
+
+  pub fn outcome(&self) -> Option<Outcome> { self.by_color_ref(GameState::outcome, GameState::outcome) }
+
+  pub fn is_over(&self) -> bool { self.by_color_ref(GameState::is_over, GameState::is_over) }
+
+  pub fn plies(&self) -> u32 { self.by_color_ref(GameState::plies, GameState::plies) }
+
+  pub fn zobrist(&self) -> u64 { self.by_color_ref(GameState::zobrist, GameState::zobrist) }
+
+  pub fn rules(&self) -> RuleSet { self.by_color_ref(GameState::rules, GameState::rules) }
+
+  pub fn with_rules(self, rules: RuleSet) -> Game {
+    self.by_color(|game| game.with_rules(rules), |game| game.with_rules(rules))
+  }
 }
 
+pub mod cgt;
+pub mod kgn;
+pub mod solve;
+pub mod tablebase;
+pub mod zobrist;
+
+pub use bitboard::{Bitboard, MAX_BITBOARD_CELLS};
+pub use builder::GameBuilder;
+pub use diagnose::Violation;
+pub use diagram::Diagram;
+pub use playout::{Playout, PlayoutSeed, RandomGames};
+pub use record::GameRecord;
+pub use regions::Region;
+pub use rules::{OpeningRule, RuleSet};
+pub use symmetry::{SYMMETRIES, Symmetry};
+
+mod bitboard;
+mod builder;
+mod diagnose;
+mod diagram;
+mod fen;
+mod notation;
+mod perft;
+mod playout;
+mod record;
+mod regions;
+mod rules;
+mod symmetry;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
 #[cfg(feature = "c-api")]
 #[doc = "false"]
 pub use c_api::*;
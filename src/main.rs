@@ -16,7 +16,7 @@
 use std::{collections, env, io};
 
 extern crate konane;
-use konane::{Game, Occupancy};
+use konane::{Game, Occupancy, RuleSet};
 use konane::Position as Pos;
 
 extern crate uuid;
@@ -48,8 +48,8 @@ const WHITE_PIECE_DATA: &'static [u8] = include_bytes!("../resources/white_piece
 const BLACK_PIECE_DATA: &'static [u8] = include_bytes!("../resources/black_piece.png");
 const EMPTY_PIECE_DATA: &'static [u8] = include_bytes!("../resources/empty_piece.png");
 
-fn main() {
-  let mut clap_app = clap::App::new("kōnane")
+fn clap_app<'a, 'b>() -> clap::App<'a, 'b> {
+  clap::App::new("kōnane")
     .version(crate_version!())
     .author(crate_authors!())
     .about("The ancient polynesian game of kōnane")
@@ -57,7 +57,31 @@ fn main() {
       .short("G")
       .long("gen-bash-completions")
       .help("Generate a bash completion file to standard output"))
-    .setting(clap::AppSettings::ColoredHelp);
+    .arg(clap::Arg::with_name("width")
+      .short("W")
+      .long("width")
+      .takes_value(true)
+      .default_value("10")
+      .help("The number of columns on the board"))
+    .arg(clap::Arg::with_name("height")
+      .short("H")
+      .long("height")
+      .takes_value(true)
+      .default_value("10")
+      .help("The number of rows on the board"))
+    .arg(clap::Arg::with_name("misere")
+      .short("m")
+      .long("misere")
+      .help("Play misère kōnane, where the last player able to move loses"))
+    .setting(clap::AppSettings::ColoredHelp)
+}
+
+fn rules(matches: &clap::ArgMatches) -> RuleSet {
+  RuleSet { misere: matches.is_present("misere"), ..RuleSet::standard() }
+}
+
+fn main() {
+  let mut clap_app = clap_app();
   let matches = clap_app.clone().get_matches();
   if matches.is_present("generate bash completions") {
     clap_app.gen_completions_to(env::args().nth(0).expect("no executable name found"),
@@ -73,10 +97,9 @@ mod errors {
     types {
       Error, ErrorKind, ChainErr, Result;
     }
-    links {
-      ::konane::errors::Error, ::konane::errors::ErrorKind, Game;
-    }
+    links {}
     foreign_links {
+      ::konane::errors::Error, Game, "kōnane game error";
       ::clap::Error, Clap, "clap error";
       ::uuid::ParseError, UUIDParse, "UUID parse error";
       ::std::io::Error, IO, "I/O error";
@@ -84,6 +107,10 @@ mod errors {
       ::gfx_core::factory::CombinedError, GFXCombined, "GFX engine combined error";
     }
     errors {
+      BoardSize(width: u8, height: u8) {
+        description("Unsupported board size")
+        display("Unsupported board size {}x{}", width, height)
+      }
       PistonGlyph(inner: ::piston_window::GlyphError) {
         description("Piston engine glyph error")
         display("Piston engine glyph error: {:?}", inner)
@@ -114,14 +141,24 @@ struct SpriteTextures {
 }
 
 fn setup(matches: clap::ArgMatches) -> errors::Result<()> {
-  let mut window: PistonWindow =
-    try!(WindowSettings::new("kōnane", [TILE_SIZE * 10, TILE_SIZE * 10]).exit_on_esc(true).build());
+  let width = try!(value_t!(matches, "width", u8));
+  let height = try!(value_t!(matches, "height", u8));
+  let mut rng = try!(StdRng::new());
+  let mut game = try!(if rng.gen() {
+                        Game::new_white_sized(width, height)
+                      } else {
+                        Game::new_black_sized(width, height)
+                      }
+                      .ok_or(errors::ErrorKind::BoardSize(width, height)));
+  game = game.with_rules(rules(&matches));
+  let window_size = [TILE_SIZE * u32::from(game.papamu().width()),
+                     TILE_SIZE * u32::from(game.papamu().height())];
+  let mut window: PistonWindow = try!(WindowSettings::new("kōnane", window_size).exit_on_esc(true).build());
   let textures = SpriteTextures {
     white_piece: try!(load_texture(WHITE_PIECE_DATA, &mut window.factory)),
     black_piece: try!(load_texture(BLACK_PIECE_DATA, &mut window.factory)),
     empty_piece: try!(load_texture(EMPTY_PIECE_DATA, &mut window.factory)),
   };
-  let mut rng = try!(StdRng::new());
   let cxt = GameContext {
     args: matches,
     textures: textures,
@@ -129,7 +166,7 @@ fn setup(matches: clap::ArgMatches) -> errors::Result<()> {
     drag_ctrl: &mut DragController::new(),
     scene: &mut Scene::new(),
     sprite_map: &mut collections::HashMap::new(),
-    game: &mut if rng.gen() { Game::new_white() } else { Game::new_black() },
+    game: &mut game,
     rng: &mut rng,
   };
   setup_scene(cxt).and_then(run)
@@ -144,8 +181,8 @@ fn load_texture(texture_data: &[u8], factory: &mut GLFactory) -> errors::Result<
 }
 
 fn setup_scene(cxt: GameContext) -> errors::Result<GameContext> {
-  for x in 0..10u8 {
-    for y in 0..10u8 {
+  for x in 0..cxt.game.papamu().width() {
+    for y in 0..cxt.game.papamu().height() {
       if (x + y) % 2 == 0 {
       } else {
       }
@@ -161,3 +198,20 @@ fn run(cxt: GameContext) -> errors::Result<()> {
   }
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use konane::RuleSet;
+
+  fn parse(args: Vec<&str>) -> Result<RuleSet, ::clap::Error> {
+    super::clap_app().get_matches_from_safe(args).map(|matches| super::rules(&matches))
+  }
+
+  #[test]
+  fn misere_flag() {
+    assert_eq!(parse(vec!["konane"]).expect("valid arguments"), RuleSet::standard());
+    assert_eq!(parse(vec!["konane", "--misere"]).expect("valid arguments"), RuleSet::misere());
+    assert_eq!(parse(vec!["konane", "-W", "6", "-m"]).expect("valid arguments"), RuleSet::misere());
+    assert!(parse(vec!["konane", "--misery"]).is_err());
+  }
+}
@@ -0,0 +1,135 @@
+// notation.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fmt, str};
+
+use {JumpPath, MAX_DIMENSION, Move, Position, errors};
+
+impl fmt::Display for Position {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    let (x, y) = <(char, u8)>::from(*self);
+    write!(fmtr, "{}{}", x.to_ascii_lowercase(), y)
+  }
+}
+
+impl fmt::Display for Move {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Move::Remove(target) => write!(fmtr, "x {}", target),
+      Move::Pass => write!(fmtr, "pass"),
+      Move::Jump { source, .. } |
+      Move::Path { source, .. } => {
+        try!(write!(fmtr, "{}", source));
+        let mut current = source;
+        for direction in self.directions() {
+          current = match current.offset(direction, 2) {
+            Some(target) => target,
+            None => return write!(fmtr, "-?"),
+          };
+          try!(write!(fmtr, "-{}", current));
+        }
+        Ok(())
+      },
+    }
+  }
+}
+
+fn bad_notation(text: &str, index: usize, expected: &'static str) -> errors::Error {
+  errors::Error::BadNotation { text: text.to_owned(), column: index + 1, expected: expected }
+}
+
+pub fn parse_position(text: &str, start: usize) -> errors::Result<(Position, usize)> {
+  let bytes = text.as_bytes();
+  let x = match bytes.get(start) {
+    Some(&file) if file.is_ascii_alphabetic() => file.to_ascii_uppercase() - b'A',
+    _ => try!(Err(bad_notation(text, start, "a file letter from a to z"))),
+  };
+  let mut end = start + 1;
+  let mut y = 0u32;
+  while end < bytes.len() && bytes[end].is_ascii_digit() {
+    y = y * 10 + u32::from(bytes[end] - b'0');
+    if y >= u32::from(MAX_DIMENSION) {
+      try!(Err(bad_notation(text, start + 1, "a rank number below 26")))
+    }
+    end += 1;
+  }
+  if end == start + 1 {
+    try!(Err(bad_notation(text, end, "a rank number")))
+  }
+  match Position::new(x, y as u8) {
+    Some(pos) => Ok((pos, end)),
+    None => Err(bad_notation(text, start, "a file letter from a to z")),
+  }
+}
+
+pub fn parse_move(text: &str) -> errors::Result<Move> {
+  let bytes = text.as_bytes();
+  if text.eq_ignore_ascii_case("pass") {
+    return Ok(Move::Pass);
+  }
+  // `Display` writes removals as "x e5"; without the space, "x" is the file of a jump's source.
+  let marked = bytes.first().map_or(false, |&marker| marker == b'x' || marker == b'X');
+  if marked && bytes.get(1).map_or(false, |space| space.is_ascii_whitespace()) {
+    let mut start = 1;
+    while bytes.get(start).map_or(false, |space| space.is_ascii_whitespace()) {
+      start += 1;
+    }
+    let (target, end) = try!(parse_position(text, start));
+    if end != bytes.len() {
+      try!(Err(bad_notation(text, end, "the end of the removal")))
+    }
+    return Ok(Move::remove(target));
+  }
+  let (source, mut end) = try!(parse_position(text, 0));
+  let mut current = source;
+  let mut path = JumpPath::new();
+  while end < bytes.len() {
+    if bytes[end] != b'-' {
+      try!(Err(bad_notation(text, end, "'-' followed by a landing square")))
+    }
+    let (target, next_end) = try!(parse_position(text, end + 1));
+    let direction = match (current.biadjacency(target), current.direction_to(target)) {
+      (Some(_), Some(direction)) => direction,
+      _ => try!(Err(bad_notation(text, end + 1, "a landing square two squares away in a straight line"))),
+    };
+    if !path.push(direction) {
+      try!(Err(bad_notation(text, end, "at most 64 jumps")))
+    }
+    current = target;
+    end = next_end;
+  }
+  if path.is_empty() {
+    try!(Err(bad_notation(text, end, "'-' followed by a landing square")))
+  }
+  Ok(Move::path(source, path))
+}
+
+impl str::FromStr for Position {
+  type Err = errors::Error;
+
+  fn from_str(text: &str) -> errors::Result<Position> {
+    let (pos, end) = try!(parse_position(text, 0));
+    if end != text.len() {
+      try!(Err(bad_notation(text, end, "the end of the position")))
+    }
+    Ok(pos)
+  }
+}
+
+impl str::FromStr for Move {
+  type Err = errors::Error;
+
+  fn from_str(text: &str) -> errors::Result<Move> { parse_move(text) }
+}
@@ -0,0 +1,51 @@
+// perft.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// As in chess, perft counts the positions exactly `depth` plies away along every sequence of legal moves;
+// a game that ends sooner contributes nothing, and `perft(0)` is always 1.
+
+use {Game, Move};
+
+impl Game {
+  pub fn perft(&self, depth: u32) -> u64 {
+    match depth {
+      0 => 1,
+      1 => self.legal_moves().count() as u64,
+      _ => {
+        self.legal_moves()
+            .map(|mv| {
+              let mut game = *self;
+              game.next_turn(mv).unwrap_or_else(|_| unreachable!());
+              game.perft(depth - 1)
+            })
+            .sum()
+      },
+    }
+  }
+
+  // The count for each legal move at the root, in `legal_moves` order; the counts add up to `perft(depth)`.
+  pub fn divide(&self, depth: u32) -> Vec<(Move, u64)> {
+    if depth == 0 {
+      return Vec::new();
+    }
+    self.legal_moves()
+        .map(|mv| {
+          let mut game = *self;
+          game.next_turn(mv).unwrap_or_else(|_| unreachable!());
+          (mv, game.perft(depth - 1))
+        })
+        .collect()
+  }
+}
@@ -0,0 +1,92 @@
+// playout.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// A playout picks uniformly among the legal moves at every ply, so it depends only on the generator it is
+// given and on the order `legal_moves` yields moves in.  Each game from `RandomGames` is played with its
+// own generator seeded from a `PlayoutSeed`, and `Game::seeded_playout` replays it from that seed alone.
+
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+use {Game, Move, Outcome};
+
+pub type PlayoutSeed = [u32; 4];
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub struct Playout {
+  pub start: Game,
+  pub moves: Vec<Move>,
+  pub end: Game,
+  pub outcome: Option<Outcome>,
+}
+
+impl Game {
+  pub fn random_playout<R: Rng>(&self, rng: &mut R) -> Playout {
+    self.random_playout_to(rng, usize::max_value())
+  }
+
+  // Stops after `max_plies` moves even if the game is not over, in which case `outcome` is `None`.
+  pub fn random_playout_to<R: Rng>(&self, rng: &mut R, max_plies: usize) -> Playout {
+    let mut game = *self;
+    let mut moves = Vec::new();
+    while moves.len() < max_plies {
+      let legal: Vec<Move> = game.legal_moves().collect();
+      let mv = match rng.choose(&legal) {
+        Some(&mv) => mv,
+        None => break,
+      };
+      game.next_turn(mv).unwrap_or_else(|_| unreachable!());
+      moves.push(mv);
+    }
+    Playout {
+      start: *self,
+      moves: moves,
+      end: game,
+      outcome: game.outcome(),
+    }
+  }
+
+  pub fn seeded_playout(&self, seed: PlayoutSeed, max_plies: usize) -> Playout {
+    self.random_playout_to(&mut XorShiftRng::from_seed(seed), max_plies)
+  }
+
+  pub fn random_games<R: Rng>(&self, rng: R, max_plies: usize) -> RandomGames<R> {
+    RandomGames {
+      start: *self,
+      rng: rng,
+      max_plies: max_plies,
+    }
+  }
+}
+
+// An endless stream of random games from the same start, each paired with the seed that replays it.
+#[derive(Clone,Debug)]
+pub struct RandomGames<R: Rng> {
+  start: Game,
+  rng: R,
+  max_plies: usize,
+}
+
+impl<R: Rng> Iterator for RandomGames<R> {
+  type Item = (PlayoutSeed, Playout);
+
+  fn next(&mut self) -> Option<(PlayoutSeed, Playout)> {
+    // XorShiftRng rejects the all-zero seed.
+    let mut seed: PlayoutSeed = self.rng.gen();
+    while seed == [0; 4] {
+      seed = self.rng.gen();
+    }
+    Some((seed, self.start.seeded_playout(seed, self.max_plies)))
+  }
+}
@@ -0,0 +1,98 @@
+// record.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::slice;
+
+use {Game, Move, errors};
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub struct GameRecord {
+  games: Vec<Game>,
+  moves: Vec<Move>,
+  ply: usize,
+}
+
+impl From<Game> for GameRecord {
+  fn from(start: Game) -> GameRecord { GameRecord::new(start) }
+}
+
+impl GameRecord {
+  pub fn new(start: Game) -> GameRecord {
+    GameRecord {
+      games: vec![start],
+      moves: Vec::new(),
+      ply: 0,
+    }
+  }
+
+  pub fn start(&self) -> &Game { &self.games[0] }
+
+  pub fn current(&self) -> &Game { &self.games[self.ply] }
+
+  pub fn ply(&self) -> usize { self.ply }
+
+  pub fn len(&self) -> usize { self.moves.len() }
+
+  pub fn is_empty(&self) -> bool { self.moves.is_empty() }
+
+  pub fn moves(&self) -> &[Move] { &self.moves }
+
+  pub fn played_moves(&self) -> &[Move] { &self.moves[..self.ply] }
+
+  pub fn position(&self, ply: usize) -> Option<&Game> { self.games.get(ply) }
+
+  pub fn positions(&self) -> slice::Iter<Game> { self.games.iter() }
+
+  pub fn push(&mut self, mv: Move) -> errors::Result<()> {
+    let mut game = *self.current();
+    try!(game.next_turn(mv));
+    self.games.truncate(self.ply + 1);
+    self.moves.truncate(self.ply);
+    self.games.push(game);
+    self.moves.push(mv);
+    self.ply += 1;
+    Ok(())
+  }
+
+  pub fn can_undo(&self) -> bool { self.ply > 0 }
+
+  pub fn can_redo(&self) -> bool { self.ply < self.moves.len() }
+
+  pub fn undo(&mut self) -> Option<Move> {
+    if self.can_undo() {
+      self.ply -= 1;
+      Some(self.moves[self.ply])
+    } else {
+      None
+    }
+  }
+
+  pub fn redo(&mut self) -> Option<Move> {
+    if self.can_redo() {
+      self.ply += 1;
+      Some(self.moves[self.ply - 1])
+    } else {
+      None
+    }
+  }
+
+  pub fn go_to(&mut self, ply: usize) -> errors::Result<()> {
+    if ply > self.moves.len() {
+      try!(Err(errors::Error::NoSuchPly { ply: ply, plies: self.moves.len() }))
+    }
+    self.ply = ply;
+    Ok(())
+  }
+}
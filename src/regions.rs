@@ -0,0 +1,193 @@
+// regions.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Every jump passes over a piece orthogonally next to the jumper and lands next to that piece, and every
+// jump removes a piece.  So a group of n pieces left to itself makes at most n - 1 jumps, and its pieces
+// never stray more than n - 1 squares (in Manhattan distance) from where the group started.  Two groups
+// can only ever interact once a piece of one is next to, or lands on, a square the other can reach; when
+// their pieces start more than n + m - 1 squares apart that can never happen, and each group can be played
+// out on its own board cut down to the squares it can reach.
+
+use {DIRECTIONS, MAX_DIMENSION, Move, Occupancy, Papamu, Position};
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+pub struct Region {
+  papamu: Papamu,
+  origin: Position,
+}
+
+impl Region {
+  pub fn papamu(&self) -> &Papamu { &self.papamu }
+
+  // The square of the full board that is a0 on the region's board.
+  pub fn origin(&self) -> Position { self.origin }
+
+  pub fn to_board(&self, pos: Position) -> Position {
+    Position {
+      x: self.origin.x + pos.x,
+      y: self.origin.y + pos.y,
+    }
+  }
+
+  pub fn from_board(&self, pos: Position) -> Option<Position> {
+    if pos.x < self.origin.x || pos.y < self.origin.y {
+      return None;
+    }
+    let pos = Position {
+      x: pos.x - self.origin.x,
+      y: pos.y - self.origin.y,
+    };
+    if self.papamu.contains(pos) { Some(pos) } else { None }
+  }
+
+  // Translates a move on the region's board into the same move on the full board.
+  pub fn move_to_board(&self, mv: Move) -> Move {
+    match mv {
+      Move::Remove(target) => Move::Remove(self.to_board(target)),
+      Move::Jump { source, direction, jumps } => {
+        Move::Jump {
+          source: self.to_board(source),
+          direction: direction,
+          jumps: jumps,
+        }
+      },
+      Move::Path { source, path } => {
+        Move::Path {
+          source: self.to_board(source),
+          path: path,
+        }
+      },
+      Move::Pass => Move::Pass,
+    }
+  }
+}
+
+fn distance(a: Position, b: Position) -> usize {
+  let dx = if a.x > b.x { a.x - b.x } else { b.x - a.x };
+  let dy = if a.y > b.y { a.y - b.y } else { b.y - a.y };
+  usize::from(dx) + usize::from(dy)
+}
+
+impl Papamu {
+  // Splits the pieces into groups that can never interact, each on a board of its own.  During the opening
+  // the whole board is a single region.
+  pub fn regions(&self) -> Vec<Region> {
+    if self.is_opening() {
+      return vec![self.region(0, 0, self.width, self.height)];
+    }
+    let mut groups = self.clusters();
+    let mut merged = true;
+    while merged {
+      merged = false;
+      'search: for first in 0..groups.len() {
+        for second in first + 1..groups.len() {
+          if self.groups_interact(&groups[first], &groups[second]) {
+            let absorbed = groups.swap_remove(second);
+            groups[first].extend(absorbed);
+            merged = true;
+            break 'search;
+          }
+        }
+      }
+    }
+    groups.sort_by_key(|group| group.iter().map(|pos| (pos.x, pos.y)).min());
+    groups.iter().map(|group| self.group_region(group)).collect()
+  }
+
+  // The orthogonally connected groups of pieces.
+  fn clusters(&self) -> Vec<Vec<Position>> {
+    let mut seen = [[false; MAX_DIMENSION as usize]; MAX_DIMENSION as usize];
+    let mut clusters = Vec::new();
+    for start in self.positions() {
+      if self[start].is_empty() || seen[usize::from(start.x)][usize::from(start.y)] {
+        continue;
+      }
+      seen[usize::from(start.x)][usize::from(start.y)] = true;
+      let mut cluster = vec![start];
+      let mut index = 0;
+      while index < cluster.len() {
+        let pos = cluster[index];
+        for &direction in &DIRECTIONS {
+          if let Some(next) = self.offset(pos, direction, 1) {
+            if self[next].is_occupied() && !seen[usize::from(next.x)][usize::from(next.y)] {
+              seen[usize::from(next.x)][usize::from(next.y)] = true;
+              cluster.push(next);
+            }
+          }
+        }
+        index += 1;
+      }
+      clusters.push(cluster);
+    }
+    clusters
+  }
+
+  fn groups_interact(&self, first: &[Position], second: &[Position]) -> bool {
+    let reach = first.len() + second.len() - 1;
+    first.iter().any(|&a| second.iter().any(|&b| distance(a, b) <= reach))
+  }
+
+  fn group_region(&self, group: &[Position]) -> Region {
+    let mut reach = group.len() - 1;
+    loop {
+      let region = self.crop(group, reach);
+      // A board with fewer than two empty squares would read as the opening, so take in more of the empty
+      // squares around the group, which its pieces can never reach anyway.
+      if !region.papamu.is_opening() {
+        return region;
+      }
+      reach += 1;
+    }
+  }
+
+  // The group alone on the part of the board within `reach` squares of its bounding box.
+  fn crop(&self, group: &[Position], reach: usize) -> Region {
+    let grow_down = |low: u8| if usize::from(low) > reach { low - reach as u8 } else { 0 };
+    let grow_up = |high: u8, limit: u8| {
+      if usize::from(limit - 1 - high) > reach { high + reach as u8 } else { limit - 1 }
+    };
+    let mut x = grow_down(group.iter().map(|pos| pos.x).min().unwrap_or(0));
+    let mut y = grow_down(group.iter().map(|pos| pos.y).min().unwrap_or(0));
+    let right = grow_up(group.iter().map(|pos| pos.x).max().unwrap_or(0), self.width);
+    let top = grow_up(group.iter().map(|pos| pos.y).max().unwrap_or(0), self.height);
+    // Keep every square the same colour on the region's board as on the full one.
+    if (x + y) % 2 == 1 {
+      if x > 0 { x -= 1 } else { y -= 1 }
+    }
+    let mut region = self.region(x, y, right - x + 1, top - y + 1);
+    for pos in region.papamu.positions() {
+      region.papamu[pos] = Occupancy::Empty;
+    }
+    for &pos in group {
+      let local = region.from_board(pos).unwrap_or_else(|| unreachable!());
+      region.papamu[local] = self[pos];
+    }
+    region
+  }
+
+  fn region(&self, x: u8, y: u8, width: u8, height: u8) -> Region {
+    let mut papamu = Papamu::new(width, height).unwrap_or_else(|| unreachable!());
+    for pos in papamu.positions() {
+      papamu[pos] = self[Position {
+                      x: x + pos.x,
+                      y: y + pos.y,
+                    }];
+    }
+    Region {
+      papamu: papamu,
+      origin: Position { x: x, y: y },
+    }
+  }
+}
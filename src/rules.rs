@@ -0,0 +1,172 @@
+// rules.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// A ruleset is written either as the name of a preset ("standard", "centre-only", ...) or as an opening
+// rule followed by any of "+turning", "+passing" and "+misere", e.g. "corner+turning".
+
+use std::{fmt, str};
+
+use {Papamu, Position, errors};
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OpeningRule {
+  CentreOrCorner,
+  Centre,
+  Corner,
+  Anywhere,
+}
+
+impl OpeningRule {
+  pub fn allows(&self, papamu: &Papamu, pos: Position) -> bool {
+    match *self {
+      OpeningRule::CentreOrCorner => papamu.is_centre(pos) || papamu.is_corner(pos),
+      OpeningRule::Centre => papamu.is_centre(pos),
+      OpeningRule::Corner => papamu.is_corner(pos),
+      OpeningRule::Anywhere => papamu.contains(pos),
+    }
+  }
+
+  fn name(&self) -> &'static str {
+    match *self {
+      OpeningRule::CentreOrCorner => "centre-or-corner",
+      OpeningRule::Centre => "centre",
+      OpeningRule::Corner => "corner",
+      OpeningRule::Anywhere => "anywhere",
+    }
+  }
+}
+
+// With `passing`, a player who has no jump passes instead of losing as long as the opponent still has one;
+// the game ends only when neither side can jump, and the player to move then loses.  With `misere`, the
+// player left without a move wins instead.
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RuleSet {
+  pub opening: OpeningRule,
+  pub turning_jumps: bool,
+  pub passing: bool,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub misere: bool,
+}
+
+const STANDARD: RuleSet = RuleSet {
+  opening: OpeningRule::CentreOrCorner,
+  turning_jumps: false,
+  passing: false,
+  misere: false,
+};
+
+const PRESETS: [(&'static str, RuleSet); 6] =
+  [("standard", STANDARD),
+   ("centre-only", RuleSet { opening: OpeningRule::Centre, ..STANDARD }),
+   ("corner-only", RuleSet { opening: OpeningRule::Corner, ..STANDARD }),
+   ("turning", RuleSet { turning_jumps: true, ..STANDARD }),
+   ("passing", RuleSet { passing: true, ..STANDARD }),
+   ("misere", RuleSet { misere: true, ..STANDARD })];
+
+impl Default for RuleSet {
+  fn default() -> RuleSet { RuleSet::standard() }
+}
+
+impl RuleSet {
+  pub fn standard() -> RuleSet { STANDARD }
+
+  pub fn centre_only() -> RuleSet { PRESETS[1].1 }
+
+  pub fn corner_only() -> RuleSet { PRESETS[2].1 }
+
+  pub fn turning() -> RuleSet { PRESETS[3].1 }
+
+  pub fn passing() -> RuleSet { PRESETS[4].1 }
+
+  pub fn misere() -> RuleSet { PRESETS[5].1 }
+
+  pub fn presets() -> &'static [(&'static str, RuleSet)] { &PRESETS }
+
+  pub fn preset(name: &str) -> Option<RuleSet> {
+    PRESETS.iter().find(|&&(preset, _)| preset == name).map(|&(_, rules)| rules)
+  }
+
+  pub fn name(&self) -> Option<&'static str> {
+    PRESETS.iter().find(|&&(_, rules)| rules == *self).map(|&(name, _)| name)
+  }
+}
+
+impl fmt::Display for RuleSet {
+  fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+    if let Some(name) = self.name() {
+      return write!(fmtr, "{}", name);
+    }
+    try!(write!(fmtr, "{}", self.opening.name()));
+    if self.turning_jumps {
+      try!(write!(fmtr, "+turning"));
+    }
+    if self.passing {
+      try!(write!(fmtr, "+passing"));
+    }
+    if self.misere {
+      try!(write!(fmtr, "+misere"));
+    }
+    Ok(())
+  }
+}
+
+impl str::FromStr for RuleSet {
+  type Err = errors::Error;
+
+  fn from_str(text: &str) -> errors::Result<RuleSet> {
+    if let Some(rules) = RuleSet::preset(text) {
+      return Ok(rules);
+    }
+    let mut parts = text.split('+');
+    let opening = match parts.next() {
+      Some("centre-or-corner") => OpeningRule::CentreOrCorner,
+      Some("centre") => OpeningRule::Centre,
+      Some("corner") => OpeningRule::Corner,
+      Some("anywhere") => OpeningRule::Anywhere,
+      _ => {
+        try!(Err(errors::Error::BadNotation {
+          text: text.to_owned(),
+          column: 1,
+          expected: "a ruleset preset or opening rule",
+        }))
+      },
+    };
+    let mut rules = RuleSet {
+      opening: opening,
+      turning_jumps: false,
+      passing: false,
+      misere: false,
+    };
+    let mut column = opening.name().len() + 1;
+    for part in parts {
+      match part {
+        "turning" if !rules.turning_jumps => rules.turning_jumps = true,
+        "passing" if !rules.passing => rules.passing = true,
+        "misere" if !rules.misere => rules.misere = true,
+        _ => {
+          try!(Err(errors::Error::BadNotation {
+            text: text.to_owned(),
+            column: column + 1,
+            expected: "\"turning\", \"passing\" or \"misere\"",
+          }))
+        },
+      }
+      column += part.len() + 1;
+    }
+    Ok(rules)
+  }
+}
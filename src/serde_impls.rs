@@ -0,0 +1,240 @@
+// serde_impls.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error as DeError;
+
+use {Direction, Game, GameState, JumpPath, MAX_DIMENSION, Move, Occupancy, Papamu, Position, RuleSet, errors,
+     turn};
+
+impl Serialize for Position {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for Position {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Position, D::Error> {
+    let text = try!(String::deserialize(deserializer));
+    text.parse().map_err(D::Error::custom)
+  }
+}
+
+impl Serialize for JumpPath {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for JumpPath {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<JumpPath, D::Error> {
+    let directions = try!(Vec::<Direction>::deserialize(deserializer));
+    JumpPath::from_directions(&directions)
+      .ok_or_else(|| D::Error::custom("a jump path holds at most 64 jumps"))
+  }
+}
+
+// A path that never turns reads back as the `Jump` that `Move::path` would have made of it.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Move")]
+enum MoveRepr {
+  Remove(Position),
+  Jump {
+    source: Position,
+    direction: Direction,
+    jumps: u8,
+  },
+  Path {
+    source: Position,
+    path: JumpPath,
+  },
+  Pass,
+}
+
+impl Serialize for Move {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match *self {
+      Move::Remove(target) => MoveRepr::Remove(target),
+      Move::Jump { source, direction, jumps } => {
+        MoveRepr::Jump {
+          source: source,
+          direction: direction,
+          jumps: jumps,
+        }
+      },
+      Move::Path { source, path } => {
+        MoveRepr::Path {
+          source: source,
+          path: path,
+        }
+      },
+      Move::Pass => MoveRepr::Pass,
+    }
+    .serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for Move {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Move, D::Error> {
+    Ok(match try!(MoveRepr::deserialize(deserializer)) {
+      MoveRepr::Remove(target) => Move::remove(target),
+      MoveRepr::Jump { source, direction, jumps } => Move::jump(source, direction, jumps),
+      MoveRepr::Path { source, path } => Move::path(source, path),
+      MoveRepr::Pass => Move::Pass,
+    })
+  }
+}
+
+// Rows run from the top of the board down, as in FEN and diagrams.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Papamu")]
+struct PapamuRepr {
+  rows: Vec<String>,
+}
+
+impl Serialize for Papamu {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let rows = (0..self.height())
+                 .rev()
+                 .map(|y| {
+                   (0..self.width())
+                     .map(|x| {
+                       match self[Position { x: x, y: y }] {
+                         Occupancy::White => 'w',
+                         Occupancy::Black => 'b',
+                         Occupancy::Empty => '.',
+                       }
+                     })
+                     .collect()
+                 })
+                 .collect();
+    PapamuRepr { rows: rows }.serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for Papamu {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Papamu, D::Error> {
+    let repr = try!(PapamuRepr::deserialize(deserializer));
+    let height = repr.rows.len();
+    let width = repr.rows.first().map_or(0, |row| row.chars().count());
+    if height > usize::from(MAX_DIMENSION) || width > usize::from(MAX_DIMENSION) {
+      return Err(D::Error::custom(format!("a board of {} by {} squares is too large", width, height)));
+    }
+    let mut papamu = try!(Papamu::new(width as u8, height as u8)
+                            .ok_or_else(|| D::Error::custom("a board must have at least one square")));
+    for (index, row) in repr.rows.iter().enumerate() {
+      if row.chars().count() != width {
+        let message = format!("row {} has {} squares instead of {}", index, row.chars().count(), width);
+        return Err(D::Error::custom(message));
+      }
+      for (x, square) in row.chars().enumerate() {
+        papamu[Position {
+          x: x as u8,
+          y: (height - 1 - index) as u8,
+        }] = match square {
+          'w' => Occupancy::White,
+          'b' => Occupancy::Black,
+          '.' => Occupancy::Empty,
+          _ => return Err(D::Error::custom(format!("{:?} is not one of 'b', 'w' or '.'", square))),
+        };
+      }
+    }
+    try!(papamu.check_parity().map_err(D::Error::custom));
+    Ok(papamu)
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "GameState")]
+struct GameStateRepr {
+  papamu: Papamu,
+  plies: u32,
+  #[serde(default)]
+  rules: RuleSet,
+}
+
+impl<Tn: turn::Turn> Serialize for GameState<Tn> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    GameStateRepr {
+        papamu: self.papamu,
+        plies: self.plies,
+        rules: self.rules,
+      }
+      .serialize(serializer)
+  }
+}
+
+impl<'de, Tn: turn::Turn> Deserialize<'de> for GameState<Tn> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<GameState<Tn>, D::Error> {
+    let repr = try!(GameStateRepr::deserialize(deserializer));
+    Ok(GameState::from_papamu(repr.papamu, repr.plies, repr.rules))
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Game")]
+struct GameRepr {
+  to_move: Occupancy,
+  papamu: Papamu,
+  plies: u32,
+  #[serde(default)]
+  rules: RuleSet,
+}
+
+impl Serialize for Game {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    GameRepr {
+        to_move: self.current_player(),
+        papamu: *self.papamu(),
+        plies: self.plies(),
+        rules: self.rules(),
+      }
+      .serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for Game {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Game, D::Error> {
+    let repr = try!(GameRepr::deserialize(deserializer));
+    match repr.to_move {
+      Occupancy::White => Ok(Game::White(GameState::from_papamu(repr.papamu, repr.plies, repr.rules))),
+      Occupancy::Black => Ok(Game::Black(GameState::from_papamu(repr.papamu, repr.plies, repr.rules))),
+      Occupancy::Empty => Err(D::Error::custom("the side to move must be white or black")),
+    }
+  }
+}
+
+// Errors are written for consumers rather than read back: the stable code and name, the message and the
+// squares involved.
+#[derive(Serialize)]
+#[serde(rename = "Error")]
+struct ErrorRepr {
+  code: u32,
+  name: &'static str,
+  message: String,
+  positions: Vec<Position>,
+}
+
+impl Serialize for errors::Error {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    ErrorRepr {
+        code: self.code(),
+        name: self.name(),
+        message: self.to_string(),
+        positions: self.positions(),
+      }
+      .serialize(serializer)
+  }
+}
@@ -0,0 +1,213 @@
+// solve.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// A `Solver` proves which player wins a game with perfect play, under whatever rules the game is played
+// by, including misère and passing.  Kōnane has no draws, so the negamax score of a position is just a win
+// or a loss for the player to move, and alpha-beta pruning comes down to cutting off the search at the
+// first move that wins.  Proven positions go into a transposition table under their Zobrist keys, shared
+// between a position and its reflections and rotations once the opening is over.  Moves are tried in order
+// of how few replies they leave the opponent, after any move the table already shows to win.
+//
+// That is enough to solve the 6x6 starting position, but not 8x8 from the start.  Proving 6x6 a win for
+// white takes about two minutes in a release build and leaves some 7.15 million positions in the table.
+//
+// A solver can be given a budget of nodes, of time or of both.  When it runs out `solve` gives up and
+// returns `None`, but everything proven so far stays in the table, so calling `solve` again picks up where
+// it left off.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use {Game, Move, Occupancy, Position, RuleSet, zobrist};
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub struct Solution {
+  pub winner: Occupancy,
+  // The moves from the solved position to the end of the game.  The winner plays moves that keep the win;
+  // the loser plays the move the solver would have searched first, not necessarily the longest defence.
+  pub principal_variation: Vec<Move>,
+}
+
+#[derive(Clone,Debug)]
+pub struct Solver {
+  node_limit: Option<u64>,
+  time_limit: Option<Duration>,
+  rules: Option<RuleSet>,
+  // Whether the player to move wins each proven position.
+  table: HashMap<u64, bool>,
+  nodes: u64,
+  deadline: Option<Instant>,
+}
+
+impl Default for Solver {
+  fn default() -> Solver { Solver::new() }
+}
+
+impl Solver {
+  pub fn new() -> Solver {
+    Solver {
+      node_limit: None,
+      time_limit: None,
+      rules: None,
+      table: HashMap::new(),
+      nodes: 0,
+      deadline: None,
+    }
+  }
+
+  // The most positions each call to `solve` may expand; positions answered from the table are free.
+  pub fn node_limit(mut self, nodes: u64) -> Solver {
+    self.node_limit = Some(nodes);
+    self
+  }
+
+  // The longest each call to `solve` may run for.
+  pub fn time_limit(mut self, limit: Duration) -> Solver {
+    self.time_limit = Some(limit);
+    self
+  }
+
+  // The positions expanded by the last call to `solve`.
+  pub fn nodes(&self) -> u64 { self.nodes }
+
+  // The positions proven so far, across every call to `solve`.
+  pub fn table_len(&self) -> usize { self.table.len() }
+
+  pub fn clear(&mut self) { self.table.clear() }
+
+  pub fn solve(&mut self, game: &Game) -> Option<Solution> {
+    // The table only holds for the rules it was filled under.
+    if self.rules != Some(game.rules()) {
+      self.table.clear();
+      self.rules = Some(game.rules());
+    }
+    self.nodes = 0;
+    self.deadline = self.time_limit.map(|limit| Instant::now() + limit);
+    let wins = match self.wins(game) {
+      Some(wins) => wins,
+      None => return None,
+    };
+    let winner = match (wins, game.current_player()) {
+      (true, Occupancy::Black) | (false, Occupancy::White) => Occupancy::Black,
+      _ => Occupancy::White,
+    };
+    Some(Solution {
+      winner: winner,
+      principal_variation: self.principal_variation(game),
+    })
+  }
+
+  // Whether the player to move wins, or `None` if the budget ran out first.
+  fn wins(&mut self, game: &Game) -> Option<bool> {
+    let key = table_key(game);
+    if let Some(&wins) = self.table.get(&key) {
+      return Some(wins);
+    }
+    if self.node_limit.map_or(false, |limit| self.nodes >= limit) ||
+       self.deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+      return None;
+    }
+    self.nodes += 1;
+
+    let children = children(game);
+    let wins = if children.is_empty() {
+      game.outcome().map_or(false, |outcome| outcome.winner == game.current_player())
+    } else if children.iter().any(|&(_, ref child)| self.table.get(&table_key(child)) == Some(&false)) {
+      true
+    } else {
+      let mut wins = false;
+      for &(_, ref child) in &children {
+        match self.wins(child) {
+          Some(false) => {
+            wins = true;
+            break;
+          },
+          Some(true) => {},
+          None => return None,
+        }
+      }
+      wins
+    };
+    self.table.insert(key, wins);
+    Some(wins)
+  }
+
+  // Every position along the way is in the table: the winner's moves lead to positions proven lost, and
+  // all the replies to a position proven lost were proven won.
+  fn principal_variation(&self, game: &Game) -> Vec<Move> {
+    let mut game = *game;
+    let mut moves = Vec::new();
+    loop {
+      let wins = self.table.get(&table_key(&game)) == Some(&true);
+      let children = children(&game);
+      let next = children.into_iter()
+                         .find(|&(_, ref child)| !wins || self.table.get(&table_key(child)) == Some(&false));
+      match next {
+        Some((mv, child)) => {
+          moves.push(mv);
+          game = child;
+        },
+        None => return moves,
+      }
+    }
+  }
+}
+
+// The positions a move away, the ones leaving the opponent the fewest replies first.
+fn children(game: &Game) -> Vec<(Move, Game)> {
+  let mut children: Vec<(Move, Game)> = game.legal_moves()
+                                             .map(|mv| {
+                                               let mut child = *game;
+                                               child.next_turn(mv).unwrap_or_else(|_| unreachable!());
+                                               (mv, child)
+                                             })
+                                             .collect();
+  children.sort_by_key(|&(_, ref child)| child.legal_moves().count());
+  children
+}
+
+// Once the opening is over, a game and its reflections and rotations are won or lost alike, even those
+// that swap the colours along with the player to move, so they share an entry under the smallest of their
+// keys.
+fn table_key(game: &Game) -> u64 {
+  if game.is_opening() {
+    return game.zobrist();
+  }
+  let papamu = game.papamu();
+  let (width, height) = (papamu.width(), papamu.height());
+  let pieces: Vec<Position> = papamu.positions().filter(|&pos| papamu[pos].is_occupied()).collect();
+  papamu.symmetries()
+        .into_iter()
+        .map(|symmetry| {
+          let (new_width, new_height) = symmetry.transformed_size(width, height);
+          let mut key = zobrist::size_key(new_width, new_height);
+          if symmetry.transform_occupancy(game.current_player(), width, height).is_black() {
+            key ^= zobrist::black_to_move_key();
+          }
+          for &pos in &pieces {
+            let target = pos.transform(symmetry, width, height).unwrap_or_else(|| unreachable!());
+            key ^= zobrist::piece_key(symmetry.transform_occupancy(papamu[pos], width, height), target);
+          }
+          key
+        })
+        .min()
+        .unwrap_or_else(|| unreachable!())
+}
+
+impl Game {
+  // Solves the game with no budget; this can take a very long time on anything but a small or nearly
+  // finished board.
+  pub fn solve(&self) -> Solution { Solver::new().solve(self).unwrap_or_else(|| unreachable!()) }
+}
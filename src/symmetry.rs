@@ -0,0 +1,201 @@
+// symmetry.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// A symmetry maps every square to another square of the same colour or every square to one of the
+// opposite colour, depending on the board's dimensions; in the latter case every piece changes colour
+// along with its square, and a transformed `Game` hands the move to the other side, so the result is
+// always the same game seen from a different angle.
+
+use {Direction, Game, GameState, JumpPath, Move, Occupancy, Papamu, Position};
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+pub enum Symmetry {
+  Identity,
+  Rotate90,
+  Rotate180,
+  Rotate270,
+  FlipHorizontal,
+  FlipVertical,
+  Transpose,
+  AntiTranspose,
+}
+
+pub const SYMMETRIES: [Symmetry; 8] = [Symmetry::Identity,
+                                       Symmetry::Rotate90,
+                                       Symmetry::Rotate180,
+                                       Symmetry::Rotate270,
+                                       Symmetry::FlipHorizontal,
+                                       Symmetry::FlipVertical,
+                                       Symmetry::Transpose,
+                                       Symmetry::AntiTranspose];
+
+impl Symmetry {
+  pub fn inverse(&self) -> Symmetry {
+    match *self {
+      Symmetry::Rotate90 => Symmetry::Rotate270,
+      Symmetry::Rotate270 => Symmetry::Rotate90,
+      symmetry => symmetry,
+    }
+  }
+
+  pub fn swaps_dimensions(&self) -> bool {
+    match *self {
+      Symmetry::Rotate90 | Symmetry::Rotate270 | Symmetry::Transpose | Symmetry::AntiTranspose => true,
+      _ => false,
+    }
+  }
+
+  pub fn preserves_size(&self, width: u8, height: u8) -> bool { width == height || !self.swaps_dimensions() }
+
+  pub fn transformed_size(&self, width: u8, height: u8) -> (u8, u8) {
+    if self.swaps_dimensions() { (height, width) } else { (width, height) }
+  }
+
+  // Every symmetry moves x + y by a constant amount modulo 2; when that amount is odd, each square lands on
+  // one of the other colour.
+  pub fn swaps_colours(&self, width: u8, height: u8) -> bool {
+    let shift = match *self {
+      Symmetry::Identity | Symmetry::Transpose => 0,
+      Symmetry::Rotate90 | Symmetry::FlipVertical => height - 1,
+      Symmetry::Rotate270 | Symmetry::FlipHorizontal => width - 1,
+      Symmetry::Rotate180 | Symmetry::AntiTranspose => width + height - 2,
+    };
+    shift % 2 == 1
+  }
+
+  pub fn transform_direction(&self, direction: Direction) -> Direction {
+    let (up, down, left, right) = match *self {
+      Symmetry::Identity => (Direction::Up, Direction::Down, Direction::Left, Direction::Right),
+      Symmetry::Rotate90 => (Direction::Left, Direction::Right, Direction::Down, Direction::Up),
+      Symmetry::Rotate180 => (Direction::Down, Direction::Up, Direction::Right, Direction::Left),
+      Symmetry::Rotate270 => (Direction::Right, Direction::Left, Direction::Up, Direction::Down),
+      Symmetry::FlipHorizontal => (Direction::Up, Direction::Down, Direction::Right, Direction::Left),
+      Symmetry::FlipVertical => (Direction::Down, Direction::Up, Direction::Left, Direction::Right),
+      Symmetry::Transpose => (Direction::Right, Direction::Left, Direction::Down, Direction::Up),
+      Symmetry::AntiTranspose => (Direction::Left, Direction::Right, Direction::Up, Direction::Down),
+    };
+    match direction {
+      Direction::Up => up,
+      Direction::Down => down,
+      Direction::Left => left,
+      Direction::Right => right,
+    }
+  }
+
+  pub fn transform_occupancy(&self, occ: Occupancy, width: u8, height: u8) -> Occupancy {
+    match occ {
+      Occupancy::White if self.swaps_colours(width, height) => Occupancy::Black,
+      Occupancy::Black if self.swaps_colours(width, height) => Occupancy::White,
+      occ => occ,
+    }
+  }
+}
+
+impl Position {
+  pub fn transform(&self, symmetry: Symmetry, width: u8, height: u8) -> Option<Position> {
+    if self.x >= width || self.y >= height {
+      return None;
+    }
+    let (x, y, w, h) = (self.x, self.y, width - 1, height - 1);
+    let (x, y) = match symmetry {
+      Symmetry::Identity => (x, y),
+      Symmetry::Rotate90 => (h - y, x),
+      Symmetry::Rotate180 => (w - x, h - y),
+      Symmetry::Rotate270 => (y, w - x),
+      Symmetry::FlipHorizontal => (w - x, y),
+      Symmetry::FlipVertical => (x, h - y),
+      Symmetry::Transpose => (y, x),
+      Symmetry::AntiTranspose => (h - y, w - x),
+    };
+    Some(Position { x: x, y: y })
+  }
+}
+
+impl Move {
+  pub fn transform(&self, symmetry: Symmetry, width: u8, height: u8) -> Option<Move> {
+    match *self {
+      Move::Remove(target) => target.transform(symmetry, width, height).map(Move::Remove),
+      Move::Jump { source, direction, jumps } => {
+        source.transform(symmetry, width, height)
+              .map(|source| Move::jump(source, symmetry.transform_direction(direction), jumps))
+      },
+      Move::Path { source, path } => {
+        let mut transformed = JumpPath::new();
+        for direction in &path {
+          transformed.push(symmetry.transform_direction(direction));
+        }
+        source.transform(symmetry, width, height).map(|source| Move::path(source, transformed))
+      },
+      Move::Pass => Some(Move::Pass),
+    }
+  }
+}
+
+impl Papamu {
+  pub fn transform(&self, symmetry: Symmetry) -> Papamu {
+    let (width, height) = symmetry.transformed_size(self.width, self.height);
+    let mut papamu = Papamu::new(width, height).unwrap_or_else(|| unreachable!());
+    for pos in self.positions() {
+      let target = pos.transform(symmetry, self.width, self.height).unwrap_or_else(|| unreachable!());
+      papamu[target] = symmetry.transform_occupancy(self[pos], self.width, self.height);
+    }
+    papamu
+  }
+
+  pub fn symmetries(&self) -> Vec<Symmetry> {
+    SYMMETRIES.iter().cloned().filter(|symmetry| symmetry.preserves_size(self.width, self.height)).collect()
+  }
+
+  fn canonical_key(&self) -> Vec<u8> {
+    self.positions()
+        .map(|pos| {
+          match self[pos] {
+            Occupancy::Empty => 0,
+            Occupancy::Black => 1,
+            Occupancy::White => 2,
+          }
+        })
+        .collect()
+  }
+
+  pub fn canonical(&self) -> (Papamu, Symmetry) {
+    self.symmetries()
+        .into_iter()
+        .map(|symmetry| (self.transform(symmetry), symmetry))
+        .min_by_key(|&(ref papamu, _)| papamu.canonical_key())
+        .unwrap_or_else(|| unreachable!())
+  }
+}
+
+impl Game {
+  pub fn transform(&self, symmetry: Symmetry) -> Game {
+    let papamu = self.papamu();
+    let player = symmetry.transform_occupancy(self.current_player(), papamu.width, papamu.height);
+    if player.is_black() {
+      Game::Black(GameState::from_papamu(papamu.transform(symmetry), self.plies(), self.rules()))
+    } else {
+      Game::White(GameState::from_papamu(papamu.transform(symmetry), self.plies(), self.rules()))
+    }
+  }
+
+  pub fn canonical(&self) -> (Game, Symmetry) {
+    self.papamu()
+        .symmetries()
+        .into_iter()
+        .map(|symmetry| (self.transform(symmetry), symmetry))
+        .min_by_key(|&(ref game, _)| (game.current_player().is_white(), game.papamu().canonical_key()))
+        .unwrap_or_else(|| unreachable!())
+  }
+}
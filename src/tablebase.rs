@@ -0,0 +1,306 @@
+// tablebase.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// An endgame `Tablebase` records, under one ruleset, whether the player to move wins every position on
+// every board of at most `cells` squares.  A square can only ever hold a piece of its own colour, so a
+// position on a `width` by `height` board comes down to the set of occupied squares, written as a number
+// whose bit `x * height + y` is set when `(x, y)` is occupied, as in `Bitboard`.  Each position is solved
+// once, from the positions a move away.
+//
+// `Game::probe` looks a game up directly when its board is small enough, and otherwise when every
+// region of the board (see `Papamu::regions`) but one is dead, since the game is then decided on that one
+// region's board alone.
+//
+// On disk a tablebase is the bytes `KTB1`; a byte holding `cells`; a byte holding the length of the
+// ruleset's name, as written by its `Display` implementation, followed by the name; and then the table
+// for every board, ordered by width and then by height.  A table holds two bits for each position,
+// whether black wins with black to move and whether white wins with white to move, packed four positions
+// to a byte starting from the lowest bits.
+
+use std::io::{Read, Write};
+
+use {Game, GameState, LegalMoves, Move, Occupancy, Papamu, Position, RuleSet, errors};
+
+pub const MAX_TABLEBASE_CELLS: usize = 25;
+
+const MAGIC: &'static [u8; 4] = b"KTB1";
+
+const WON: u8 = 1;
+const LOST: u8 = 2;
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub struct Tablebase {
+  cells: usize,
+  rules: RuleSet,
+  tables: Vec<Table>,
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+struct Table {
+  width: u8,
+  height: u8,
+  bits: Vec<u8>,
+}
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+pub struct Probe {
+  pub winner: Occupancy,
+  // A move that keeps the win for the winner; for the loser every move loses, and this is the first legal
+  // one.  `None` once the game is over.
+  pub best_move: Option<Move>,
+}
+
+impl Tablebase {
+  // Builds the tables for every board of up to `cells` squares, or returns `None` if `cells` is more than
+  // `MAX_TABLEBASE_CELLS`.  The largest boards dominate: each extra square doubles the work.
+  pub fn build(cells: usize, rules: RuleSet) -> Option<Tablebase> {
+    if cells > MAX_TABLEBASE_CELLS {
+      return None;
+    }
+    let tables = board_sizes(cells).into_iter()
+                                   .map(|(width, height)| Builder::new(width, height, rules).build())
+                                   .collect();
+    Some(Tablebase {
+      cells: cells,
+      rules: rules,
+      tables: tables,
+    })
+  }
+
+  pub fn cells(&self) -> usize { self.cells }
+
+  pub fn rules(&self) -> RuleSet { self.rules }
+
+  pub fn covers(&self, papamu: &Papamu) -> bool {
+    usize::from(papamu.width()) * usize::from(papamu.height()) <= self.cells
+  }
+
+  // Whether `player` wins with the move on `papamu`, if the tablebase covers it.
+  pub fn wins(&self, papamu: &Papamu, player: Occupancy) -> Option<bool> {
+    let shift = match player {
+      Occupancy::Black => 0,
+      Occupancy::White => 1,
+      Occupancy::Empty => return None,
+    };
+    self.tables
+        .iter()
+        .find(|table| table.width == papamu.width() && table.height == papamu.height())
+        .map(|table| {
+          let set = occupied_set(papamu);
+          (table.bits[set / 4] >> (2 * (set % 4) + shift)) & 1 == 1
+        })
+  }
+
+  pub fn write_to<W: Write>(&self, out: &mut W) -> errors::Result<()> {
+    let rules = self.rules.to_string();
+    try!(out.write_all(MAGIC));
+    try!(out.write_all(&[self.cells as u8, rules.len() as u8]));
+    try!(out.write_all(rules.as_bytes()));
+    for table in &self.tables {
+      try!(out.write_all(&table.bits));
+    }
+    Ok(())
+  }
+
+  pub fn read_from<R: Read>(mut input: R) -> errors::Result<Tablebase> {
+    let mut header = [0; 6];
+    try!(input.read_exact(&mut header));
+    if &header[..4] != &MAGIC[..] {
+      try!(Err(errors::Error::BadTablebase { expected: "the bytes \"KTB1\"" }));
+    }
+    let cells = usize::from(header[4]);
+    if cells > MAX_TABLEBASE_CELLS {
+      try!(Err(errors::Error::BadTablebase { expected: "at most 25 cells" }));
+    }
+    let mut name = vec![0; usize::from(header[5])];
+    try!(input.read_exact(&mut name));
+    let rules = try!(String::from_utf8(name)
+                       .ok()
+                       .and_then(|name| name.parse().ok())
+                       .ok_or(errors::Error::BadTablebase { expected: "the name of a ruleset" }));
+    let mut tables = Vec::new();
+    for (width, height) in board_sizes(cells) {
+      let mut bits = vec![0; table_len(width, height)];
+      try!(input.read_exact(&mut bits));
+      tables.push(Table {
+        width: width,
+        height: height,
+        bits: bits,
+      });
+    }
+    if try!(input.read(&mut [0])) != 0 {
+      try!(Err(errors::Error::BadTablebase { expected: "the end of the tablebase" }));
+    }
+    Ok(Tablebase {
+      cells: cells,
+      rules: rules,
+      tables: tables,
+    })
+  }
+
+  fn probe_board(&self, papamu: &Papamu, player: Occupancy) -> Option<(bool, Option<Move>)> {
+    let wins = match self.wins(papamu, player) {
+      Some(wins) => wins,
+      None => return None,
+    };
+    let game = game_on(*papamu, player, self.rules);
+    let best_move = game.legal_moves()
+                        .find(|&mv| {
+                          let mut child = game;
+                          child.next_turn(mv).unwrap_or_else(|_| unreachable!());
+                          !wins || self.wins(child.papamu(), child.current_player()) == Some(false)
+                        });
+    Some((wins, best_move))
+  }
+}
+
+impl Game {
+  // The exact outcome and a best move, if the tablebase covers the game; `None` otherwise, or if the
+  // tablebase was built for other rules.
+  pub fn probe(&self, tablebase: &Tablebase) -> Option<Probe> {
+    if self.rules() != tablebase.rules {
+      return None;
+    }
+    let player = self.current_player();
+    let found = if tablebase.covers(self.papamu()) {
+      tablebase.probe_board(self.papamu(), player)
+    } else {
+      let regions = self.papamu().regions();
+      let mut live = regions.iter().filter(|region| {
+        let papamu = region.papamu();
+        LegalMoves::new(papamu, Occupancy::Black, self.rules()).next().is_some() ||
+        LegalMoves::new(papamu, Occupancy::White, self.rules()).next().is_some()
+      });
+      match (live.next(), live.next()) {
+        (None, _) => Some((self.outcome().map_or(false, |outcome| outcome.winner == player), None)),
+        (Some(region), None) => {
+          tablebase.probe_board(region.papamu(), player)
+                   .map(|(wins, best_move)| (wins, best_move.map(|mv| region.move_to_board(mv))))
+        },
+        (Some(_), Some(_)) => None,
+      }
+    };
+    found.map(|(wins, best_move)| {
+      Probe {
+        winner: if wins == player.is_black() { Occupancy::Black } else { Occupancy::White },
+        best_move: best_move,
+      }
+    })
+  }
+}
+
+// Solves every position on one board, remembering for each side to move whether it is won or lost.
+struct Builder {
+  width: u8,
+  height: u8,
+  rules: RuleSet,
+  states: Vec<u8>,
+}
+
+impl Builder {
+  fn new(width: u8, height: u8, rules: RuleSet) -> Builder {
+    Builder {
+      width: width,
+      height: height,
+      rules: rules,
+      states: vec![0; 1 << (usize::from(width) * usize::from(height))],
+    }
+  }
+
+  fn build(mut self) -> Table {
+    let mut bits = vec![0; table_len(self.width, self.height)];
+    for set in 0..self.states.len() {
+      for (shift, &player) in [Occupancy::Black, Occupancy::White].iter().enumerate() {
+        if self.wins(set, player) {
+          bits[set / 4] |= 1 << (2 * (set % 4) + shift);
+        }
+      }
+    }
+    Table {
+      width: self.width,
+      height: self.height,
+      bits: bits,
+    }
+  }
+
+  fn wins(&mut self, set: usize, player: Occupancy) -> bool {
+    let shift = if player.is_black() { 0 } else { 2 };
+    match (self.states[set] >> shift) & 3 {
+      WON => return true,
+      LOST => return false,
+      _ => {},
+    }
+    let game = game_on(papamu_of(self.width, self.height, set), player, self.rules);
+    let moves: Vec<Move> = game.legal_moves().collect();
+    let wins = if moves.is_empty() {
+      game.outcome().map_or(false, |outcome| outcome.winner == player)
+    } else {
+      let mut wins = false;
+      for mv in moves {
+        let mut child = game;
+        child.next_turn(mv).unwrap_or_else(|_| unreachable!());
+        if !self.wins(occupied_set(child.papamu()), child.current_player()) {
+          wins = true;
+          break;
+        }
+      }
+      wins
+    };
+    self.states[set] |= (if wins { WON } else { LOST }) << shift;
+    wins
+  }
+}
+
+fn board_sizes(cells: usize) -> Vec<(u8, u8)> {
+  let mut sizes = Vec::new();
+  for width in 1..cells + 1 {
+    for height in 1..cells / width + 1 {
+      sizes.push((width as u8, height as u8));
+    }
+  }
+  sizes
+}
+
+fn table_len(width: u8, height: u8) -> usize { ((1 << (usize::from(width) * usize::from(height))) + 3) / 4 }
+
+fn occupied_set(papamu: &Papamu) -> usize {
+  let height = usize::from(papamu.height());
+  papamu.positions()
+        .filter(|&pos| papamu[pos].is_occupied())
+        .fold(0, |set, pos| set | 1 << (usize::from(pos.x) * height + usize::from(pos.y)))
+}
+
+fn papamu_of(width: u8, height: u8, set: usize) -> Papamu {
+  let mut papamu = Papamu::new(width, height).unwrap_or_else(|| unreachable!());
+  for x in 0..width {
+    for y in 0..height {
+      let pos = Position { x: x, y: y };
+      if set >> (usize::from(x) * usize::from(height) + usize::from(y)) & 1 == 1 {
+        papamu[pos] = papamu.square_colour(pos);
+      } else {
+        papamu[pos] = Occupancy::Empty;
+      }
+    }
+  }
+  papamu
+}
+
+fn game_on(papamu: Papamu, player: Occupancy, rules: RuleSet) -> Game {
+  if player.is_black() {
+    Game::Black(GameState::from_papamu(papamu, 0, rules))
+  } else {
+    Game::White(GameState::from_papamu(papamu, 0, rules))
+  }
+}
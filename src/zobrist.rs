@@ -0,0 +1,61 @@
+// zobrist.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The key table is fixed: entry n is splitmix64(ZOBRIST_SEED + n), where splitmix64 is the finalizer from
+// Steele, Lea and Flood's SplitMix generator. Entry 0 is the black-to-move key, entry
+// 1 + (c * MAX_DIMENSION + x) * MAX_DIMENSION + y is a piece at (x, y), with c = 0 for black and c = 1 for
+// white, and entry 1 + 2 * MAX_DIMENSION * MAX_DIMENSION + w * (MAX_DIMENSION + 1) + h is a w by h board.
+// Keys therefore never depend on the build, the platform or the process.
+
+use {MAX_DIMENSION, Occupancy, Papamu, Position};
+
+pub const ZOBRIST_SEED: u64 = 0x4B4F_4E41_4E45;
+
+fn splitmix64(seed: u64) -> u64 {
+  let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+  z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+  z ^ (z >> 31)
+}
+
+fn table_entry(index: u64) -> u64 { splitmix64(ZOBRIST_SEED.wrapping_add(index)) }
+
+pub fn black_to_move_key() -> u64 { table_entry(0) }
+
+pub fn piece_key(occupancy: Occupancy, pos: Position) -> u64 {
+  let dimension = u64::from(MAX_DIMENSION);
+  let colour = match occupancy {
+    Occupancy::Black => 0,
+    Occupancy::White => 1,
+    Occupancy::Empty => return 0,
+  };
+  table_entry(1 + (colour * dimension + u64::from(pos.x())) * dimension + u64::from(pos.y()))
+}
+
+pub fn size_key(width: u8, height: u8) -> u64 {
+  let dimension = u64::from(MAX_DIMENSION);
+  table_entry(1 + 2 * dimension * dimension + u64::from(width) * (dimension + 1) + u64::from(height))
+}
+
+pub fn papamu_key(papamu: &Papamu) -> u64 {
+  let mut key = size_key(papamu.width(), papamu.height());
+  for x in 0..papamu.width() {
+    for y in 0..papamu.height() {
+      let pos = Position::new(x, y).unwrap_or_else(|| unreachable!());
+      key ^= piece_key(papamu[pos], pos);
+    }
+  }
+  key
+}
@@ -0,0 +1,117 @@
+// bitboard.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// Boards of up to 128 squares generate their moves from a `Bitboard`; these tests hold it to a plain
+// square-by-square reading of the `Papamu`, on boards down to a single file or rank.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Bitboard, Direction, Game, MAX_BITBOARD_CELLS, Move, Occupancy, Papamu, Position};
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+const SIZES: [(u8, u8); 9] = [(1, 26), (26, 1), (1, 7), (11, 11), (8, 16), (16, 8), (2, 26), (5, 7), (6, 6)];
+
+fn can_jump(papamu: &Papamu, player: Occupancy, source: Position, direction: Direction, jumps: u8) -> bool {
+  if papamu[source] != player {
+    return false;
+  }
+  let mut current = source;
+  for _ in 0..jumps {
+    match (papamu.offset(current, direction, 1), papamu.offset(current, direction, 2)) {
+      (Some(mid), Some(target)) if papamu[mid].is_occupied() && papamu[mid] != player &&
+                                   papamu[target].is_empty() => current = target,
+      _ => return false,
+    }
+  }
+  jumps > 0
+}
+
+fn reference_moves(papamu: &Papamu, player: Occupancy) -> Vec<Move> {
+  let mut moves = Vec::new();
+  for x in 0..papamu.width() {
+    for y in 0..papamu.height() {
+      let source = Position::new(x, y).expect("position on the board");
+      for &direction in &DIRECTIONS {
+        let mut jumps = 1;
+        while can_jump(papamu, player, source, direction, jumps) {
+          moves.push(Move::jump(source, direction, jumps));
+          jumps += 1;
+        }
+      }
+    }
+  }
+  moves
+}
+
+fn check(game: &Game) {
+  let papamu = game.papamu();
+  let bitboard = papamu.bitboard().expect("small enough for a bitboard");
+  assert_eq!(bitboard.to_papamu(), *papamu);
+  let max_jumps = papamu.width().max(papamu.height()) / 2 + 1;
+  for &player in &[Occupancy::Black, Occupancy::White] {
+    let mut movable = 0u128;
+    for x in 0..papamu.width() {
+      for y in 0..papamu.height() {
+        let source = Position::new(x, y).expect("position on the board");
+        assert_eq!(bitboard[source], papamu[source]);
+        for &direction in &DIRECTIONS {
+          for jumps in 0..max_jumps + 1 {
+            let expected = can_jump(papamu, player, source, direction, jumps);
+            assert_eq!(bitboard.can_jump(player, source, direction, jumps), expected,
+                       "{} {} {:?} x{}\n{}", player, source, direction, jumps, papamu);
+            if expected {
+              movable |= bitboard.bit(source);
+            }
+          }
+        }
+      }
+    }
+    assert_eq!(bitboard.movable(player), movable, "{}\n{}", player, papamu);
+  }
+  assert_eq!(game.legal_moves().collect::<Vec<_>>(), reference_moves(papamu, game.current_player()));
+}
+
+#[test]
+fn bitboards_match_the_board() {
+  let mut rng = XorShiftRng::from_seed([6, 28, 496, 8128]);
+  for &(width, height) in &SIZES {
+    for _ in 0..10 {
+      let mut game = Game::new_black_sized(width, height).expect("valid board size");
+      loop {
+        let moves: Vec<Move> = game.legal_moves().collect();
+        if !game.is_opening() {
+          check(&game);
+        }
+        if moves.is_empty() {
+          break;
+        }
+        game.next_turn(moves[rng.gen_range(0, moves.len())]).expect("legal move");
+      }
+    }
+  }
+}
+
+#[test]
+fn only_small_boards() {
+  for &(width, height) in &SIZES {
+    assert!(usize::from(width) * usize::from(height) <= MAX_BITBOARD_CELLS);
+  }
+  assert!(Bitboard::new(&Papamu::new(12, 11).expect("valid board size")).is_none());
+  assert!(Bitboard::new(&Papamu::new(26, 26).expect("valid board size")).is_none());
+}
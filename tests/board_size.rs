@@ -0,0 +1,83 @@
+// board_size.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// Boards run from 1x1 to 26x26, and squares past the chosen width and height are off the board.
+
+extern crate konane;
+
+use konane::{Game, Move, Occupancy, Papamu, Position};
+use konane::errors::Error;
+
+fn pos(text: &str) -> Position { text.parse().expect("valid position") }
+
+#[test]
+fn sizes() {
+  for &(width, height) in &[(0, 6), (6, 0), (27, 6), (6, 27)] {
+    assert!(Papamu::new(width, height).is_none(), "{}x{}", width, height);
+  }
+  for &(width, height) in &[(1, 1), (6, 6), (8, 3), (26, 26)] {
+    let papamu = Papamu::new(width, height).expect("valid board size");
+    assert_eq!((papamu.width(), papamu.height()), (width, height));
+    assert!(papamu.position(width - 1, height - 1).is_some());
+    assert!(papamu.position(width, 0).is_none());
+    assert!(papamu.position(0, height).is_none());
+  }
+  assert_eq!(Papamu::default(), Papamu::new(10, 10).expect("valid board size"));
+}
+
+#[test]
+fn rectangular_boards() {
+  let papamu = Papamu::new(8, 3).expect("valid board size");
+  assert!(papamu.contains(pos("h2")));
+  assert!(!papamu.contains(pos("h3")));
+  assert!(!papamu.contains(pos("i0")));
+  assert_eq!(papamu[pos("h2")], Occupancy::White);
+  assert_eq!(papamu[pos("g2")], Occupancy::Black);
+}
+
+#[test]
+#[should_panic(expected = "g0 is off the 6x6 board")]
+fn reading_off_the_board() {
+  let papamu = Papamu::new(6, 6).expect("valid board size");
+  let _ = papamu[pos("g0")];
+}
+
+#[test]
+#[should_panic(expected = "a6 is off the 6x6 board")]
+fn writing_off_the_board() {
+  let mut papamu = Papamu::new(6, 6).expect("valid board size");
+  papamu[pos("a6")] = Occupancy::Empty;
+}
+
+#[test]
+fn moves_from_off_the_board() {
+  let mut game = Game::new_black_sized(6, 6).expect("valid board size");
+  match game.next_turn(Move::remove(pos("g0"))) {
+    Err(Error::EmptySource { source_pos }) => assert_eq!(source_pos, pos("g0")),
+    other => panic!("expected EmptySource, got {:?}", other),
+  }
+  for mv in &["x a0", "x b0"] {
+    game.next_turn(mv.parse().expect("valid move")).expect("legal move");
+  }
+  match game.next_turn("a8-a6".parse().expect("valid move")) {
+    Err(Error::EmptySource { source_pos }) => assert_eq!(source_pos, pos("a8")),
+    other => panic!("expected EmptySource, got {:?}", other),
+  }
+  match game.jump_chain(pos("a8"), &[pos("a6"), pos("c6")]) {
+    Err(Error::TurningJump { source_occ: Occupancy::Empty, .. }) => {},
+    other => panic!("expected TurningJump, got {:?}", other),
+  }
+}
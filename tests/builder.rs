@@ -0,0 +1,87 @@
+// builder.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// `GameBuilder` sets up arbitrary positions, and refuses ones that could never arise: a piece on a
+// square of the other colour, a square off the board or a board of an unsupported size.
+
+extern crate konane;
+
+use konane::{Game, GameBuilder, Occupancy, Position, RuleSet};
+use konane::errors::Error;
+
+fn pos(text: &str) -> Position { text.parse().expect("valid position") }
+
+#[test]
+fn empty_and_full_boards() {
+  let empty = GameBuilder::new(4, 3).build_papamu().expect("valid position");
+  for x in 0..4 {
+    for y in 0..3 {
+      assert!(empty[Position::new(x, y).expect("position on the board")].is_empty());
+    }
+  }
+  let full = GameBuilder::new(4, 3).full().build().expect("valid position");
+  assert_eq!(full, Game::new_black_sized(4, 3).expect("valid board size"));
+}
+
+#[test]
+fn placements_survive_full() {
+  let before = GameBuilder::new(6, 6).remove(pos("a0")).full().remove(pos("a1"));
+  let after = GameBuilder::new(6, 6).full().remove(pos("a0")).remove(pos("a1"));
+  let (before, after) = (before.build().expect("valid position"), after.build().expect("valid position"));
+  assert_eq!(before, after);
+  assert!(before.papamu()[pos("a0")].is_empty());
+  assert!(before.papamu()[pos("a1")].is_empty());
+  assert_eq!(before.papamu()[pos("a2")], Occupancy::Black);
+}
+
+#[test]
+fn from_a_game() {
+  let mut game = Game::new_black_sized(5, 7).expect("valid board size").with_rules(RuleSet::turning());
+  for mv in &["x a0", "x a1", "c0-a0"] {
+    game.next_turn(mv.parse().expect("valid move")).expect("legal move");
+  }
+  assert_eq!(GameBuilder::from(game).build().expect("valid position"), game);
+}
+
+#[test]
+fn misplaced_pieces() {
+  match GameBuilder::new(6, 6).place(pos("b0"), Occupancy::Black).build() {
+    Err(Error::MisplacedPiece { occ: Occupancy::Black, pos: square }) => assert_eq!(square, pos("b0")),
+    other => panic!("expected MisplacedPiece, got {:?}", other),
+  }
+  match GameBuilder::new(6, 6).full().place(pos("c2"), Occupancy::White).build() {
+    Err(Error::MisplacedPiece { occ: Occupancy::White, pos: square }) => assert_eq!(square, pos("c2")),
+    other => panic!("expected MisplacedPiece, got {:?}", other),
+  }
+}
+
+#[test]
+fn bad_sizes_and_squares() {
+  for &(width, height) in &[(0, 6), (6, 0), (27, 6), (6, 27)] {
+    match GameBuilder::new(width, height).build() {
+      Err(Error::BadBoardSize { width: w, height: h }) => assert_eq!((w, h), (width, height)),
+      other => panic!("expected BadBoardSize for {}x{}, got {:?}", width, height, other),
+    }
+  }
+  match GameBuilder::new(6, 6).place(pos("g0"), Occupancy::Black).build() {
+    Err(Error::OffBoard { pos: square, width: 6, height: 6 }) => assert_eq!(square, pos("g0")),
+    other => panic!("expected OffBoard, got {:?}", other),
+  }
+  match GameBuilder::new(6, 6).to_move(Occupancy::Empty).build() {
+    Err(Error::EmptyTurn) => {},
+    other => panic!("expected EmptyTurn, got {:?}", other),
+  }
+}
@@ -0,0 +1,136 @@
+// cgt.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Canonical values of small positions, worked out by hand from their options.  Black is Left and white is
+// Right.
+
+extern crate konane;
+
+use konane::Papamu;
+use konane::cgt::{Dyadic, Form, Value};
+
+const NO_MOVES: &'static str = " 3 .
+ 2 .
+ 1 .
+ 0 .
+   A";
+
+// Each side can jump the other, leaving a lone piece: {0 | 0}.
+const STAR: &'static str = " 3 .
+ 2 x
+ 1 o
+ 0 .
+   A";
+
+// Only black can jump: {0 | }.
+const ONE: &'static str = " 3 .
+ 2 .
+ 1 o
+ 0 x
+   A";
+
+// Only white can jump: { | 0}.
+const MINUS_ONE: &'static str = " 3 o
+ 2 x
+ 1 .
+ 0 .
+   A";
+
+// Black jumps to 0; white jumps to a position where black then has a move of its own: {0 | 1}.
+const ONE_HALF: &'static str = " 4 .
+ 3 o
+ 2 x
+ 1 .
+ 0 x
+   A";
+
+const TWO: &'static str = " 1 o x o .
+ 0 x o . .
+   A B C D";
+
+// * at the top and ↑* at the bottom, too far apart to meet, add up to ↑.
+const UP: &'static str = "11 . . . .
+10 x o . .
+ 9 . x . .
+ 8 . . . .
+ 7 . . . .
+ 6 . . . .
+ 5 . . . .
+ 4 . . . .
+ 3 . . . .
+ 2 x o . .
+ 1 o . o .
+ 0 . . . .
+   A B C D";
+
+// Likewise -1 at the top and 1±1 at the bottom add up to ±1.
+const PLUS_MINUS_ONE: &'static str = "11 o x . .
+10 x . . .
+ 9 . . . .
+ 8 . . . .
+ 7 . . . .
+ 6 . . . .
+ 5 . . . .
+ 4 . . . .
+ 3 . . . .
+ 2 . o . .
+ 1 o . . .
+ 0 . o x .
+   A B C D";
+
+fn board(diagram: &str) -> Papamu { Papamu::from_diagram(diagram).expect("valid diagram") }
+
+#[test]
+fn numbers() {
+  assert_eq!(board(NO_MOVES).value(), Value::zero());
+  assert_eq!(board(ONE).value(), Value::integer(1));
+  assert_eq!(board(MINUS_ONE).value(), Value::integer(-1));
+  assert_eq!(board(TWO).value(), Value::integer(2));
+  assert_eq!(board(ONE_HALF).value(), Value::number(Dyadic::new(1, 1)));
+  assert_eq!(board(ONE_HALF).value().to_string(), "1/2");
+}
+
+#[test]
+fn star() {
+  let star = board(STAR).value();
+  assert_eq!(star, Value::star());
+  assert_eq!(star.form(), Form::Nimber(Dyadic::integer(0), 1));
+  assert!((star.clone() + star).is_zero());
+}
+
+#[test]
+fn up() {
+  let up = board(UP).value();
+  assert_eq!(up, Value::up());
+  assert_eq!(format!("{:#}", up), "↑");
+  assert!(up.is_infinitesimal());
+  let parts: Vec<Value> = board(UP).regions().iter().map(|region| region.papamu().value()).collect();
+  assert_eq!(parts, vec![Value::up() + Value::star(), Value::star()]);
+  assert_eq!(parts[0].clone() + parts[1].clone(), up);
+}
+
+#[test]
+fn switch() {
+  let switch = board(PLUS_MINUS_ONE).value();
+  assert_eq!(switch, Value::switch(Dyadic::integer(1), Dyadic::integer(-1)));
+  assert_eq!(switch.form(), Form::Switch(Dyadic::integer(0), Dyadic::integer(1)));
+  assert_eq!(format!("{:#}", switch), "±1");
+  let parts: Vec<Value> = board(PLUS_MINUS_ONE).regions()
+                                                .iter()
+                                                .map(|region| region.papamu().value())
+                                                .collect();
+  assert_eq!(parts, vec![Value::switch(Dyadic::integer(2), Dyadic::integer(0)), Value::integer(-1)]);
+  assert_eq!(parts[0].clone() + parts[1].clone(), switch);
+}
@@ -0,0 +1,135 @@
+// diagnose.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Direction, Game, JumpPath, Move, Occupancy, Position, RuleSet, Violation};
+use konane::errors::Error;
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+// A 6x6 game just past the opening, with black to move.
+fn opened() -> Game {
+  let mut game = Game::new_black_sized(6, 6).expect("valid board size");
+  for mv in &["x a0", "x b0"] {
+    game.next_turn(parse(mv)).expect("legal move");
+  }
+  game
+}
+
+fn parse(mv: &str) -> Move { mv.parse().expect("valid move") }
+
+fn squares(violation: &Violation) -> Vec<String> {
+  violation.positions.iter().map(|pos| pos.to_string()).collect()
+}
+
+#[test]
+fn several_rules_broken_by_one_move() {
+  let game = opened();
+  // a1 holds a white piece, a2 a black one and a3 another white one.
+  let mv = parse("a1-a3");
+  let violations = game.diagnose(mv);
+  assert_eq!(violations.len(), 3, "{:?}", violations);
+  match violations[0].kind {
+    Error::WrongColor { source_occ: Occupancy::White, desired_occ: Occupancy::Black, .. } => {},
+    ref other => panic!("expected WrongColor, got {:?}", other),
+  }
+  match violations[1].kind {
+    Error::OccupiedTarget { .. } => {},
+    ref other => panic!("expected OccupiedTarget, got {:?}", other),
+  }
+  match violations[2].kind {
+    Error::IllegalJump { mid_occ: Occupancy::Black, .. } => {},
+    ref other => panic!("expected IllegalJump, got {:?}", other),
+  }
+  let squares: Vec<Vec<String>> = violations.iter().map(squares).collect();
+  assert_eq!(squares, vec![vec!["a1"], vec!["a3"], vec!["a1", "a2", "a3"]]);
+  assert_eq!(violations.iter().map(|violation| violation.jump).collect::<Vec<_>>(),
+             vec![None, Some(1), Some(1)]);
+  // `next_turn` reports one of them.
+  let mut next = game;
+  let error = next.next_turn(mv).expect_err("illegal move").to_string();
+  assert!(violations.iter().any(|violation| violation.kind.to_string() == error), "{}", error);
+}
+
+#[test]
+fn jumping_during_the_opening() {
+  let game = Game::new_black_sized(6, 6).expect("valid board size");
+  let messages: Vec<String> =
+    game.diagnose(parse("a2-a0")).iter().map(|violation| violation.kind.to_string()).collect();
+  assert_eq!(messages,
+             vec!["Cannot jump from a2 before the opening removals are complete",
+                  "The target a0 is not empty"]);
+}
+
+#[test]
+fn legal_moves_break_no_rules() {
+  let game = opened();
+  for mv in game.legal_moves() {
+    assert!(game.diagnose(mv).is_empty(), "{}", mv);
+  }
+}
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+// Any move at all, from anywhere on the board or just off it.
+fn random_move<R: Rng>(rng: &mut R, game: &Game) -> Move {
+  let (width, height) = (game.papamu().width(), game.papamu().height());
+  let source = Position::new(rng.gen_range(0, width + 2), rng.gen_range(0, height + 2))
+    .expect("position on the largest board");
+  match rng.gen_range(0, 8) {
+    0 => Move::Pass,
+    1 | 2 => Move::remove(source),
+    3 | 4 => Move::jump(source, *rng.choose(&DIRECTIONS).expect("a direction"), rng.gen_range(0, 4)),
+    _ => {
+      let directions: Vec<Direction> =
+        (0..rng.gen_range(0, 4)).map(|_| *rng.choose(&DIRECTIONS).expect("a direction")).collect();
+      Move::path(source, JumpPath::from_directions(&directions).expect("short path"))
+    },
+  }
+}
+
+#[test]
+fn diagnoses_agree_with_next_turn() {
+  let mut rng = XorShiftRng::from_seed([18, 18, 18, 18]);
+  for &(_, rules) in RuleSet::presets() {
+    for _ in 0..10 {
+      let mut game = Game::new_black_sized(6, 5).expect("valid board size").with_rules(rules);
+      loop {
+        let mut candidates: Vec<Move> = (0..30).map(|_| random_move(&mut rng, &game)).collect();
+        candidates.extend(game.legal_moves());
+        for mv in candidates {
+          let violations = game.diagnose(mv);
+          let mut next = game;
+          match next.next_turn(mv) {
+            Ok(()) => assert!(violations.is_empty(), "{}: {:?}", mv, violations),
+            Err(err) => {
+              assert!(violations.iter().any(|violation| violation.kind.to_string() == err.to_string()),
+                      "{}: {} not among {:?}",
+                      mv,
+                      err,
+                      violations)
+            },
+          }
+        }
+        let moves: Vec<Move> = game.legal_moves().collect();
+        match rng.choose(&moves) {
+          Some(&mv) => game.next_turn(mv).expect("legal move"),
+          None => break,
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,96 @@
+// diagram.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Diagrams are exact: these fixtures are what `Display` prints, character for character, and each one
+// reads back as the board it was drawn from.
+
+extern crate konane;
+
+use konane::{Game, Move, Occupancy, Papamu, Position};
+
+const OPENED: &'static str = " 3 o x o x
+ 2 x o . o
+ 1 o x o x
+ 0 x o x .
+   A B C D";
+
+const OPENED_UNICODE: &'static str = " 3 ○ ● ○ ●
+ 2 ● ○ · ○
+ 1 ○ ● ○ ●
+ 0 ● ○ ● ·
+   A B C D
+black to move";
+
+// Two highlighted squares side by side share a parenthesis: the second one's "(" stands in for the
+// first one's ")".
+const ADJACENT_HIGHLIGHTS: &'static str = " 3 o x o x
+ 2 x(o(.)o
+ 1 o x o x
+ 0(x)o x .
+   A B C D";
+
+const BEFORE_JUMP: &'static str = " 3 o x o x
+ 2 x o x o
+ 1 o . o x
+ 0 x o x .
+   A B C D
+white to move";
+
+const AFTER_JUMP: &'static str = " 3 o x o x
+ 2 x o x o
+ 1 o . o x
+ 0 x(.).(o)
+   A B C D";
+
+fn pos(x: u8, y: u8) -> Position { Position::new(x, y).expect("position on the board") }
+
+fn opened() -> Papamu {
+  let mut papamu = Papamu::new(4, 4).expect("valid board size");
+  papamu[pos(2, 2)] = Occupancy::Empty;
+  papamu[pos(3, 0)] = Occupancy::Empty;
+  papamu
+}
+
+#[test]
+fn ascii_round_trip() {
+  let papamu = opened();
+  assert_eq!(papamu.to_string(), OPENED);
+  assert_eq!(Papamu::from_diagram(OPENED).expect("valid diagram"), papamu);
+}
+
+#[test]
+fn unicode_round_trip() {
+  let game = Game::from_diagram(OPENED_UNICODE).expect("valid diagram");
+  assert_eq!(game.current_player(), Occupancy::Black);
+  assert_eq!(*game.papamu(), opened());
+  assert_eq!(format!("{:#}", game), OPENED_UNICODE);
+}
+
+#[test]
+fn adjacent_highlights() {
+  let papamu = opened();
+  let diagram = papamu.diagram().highlight(pos(1, 2)).highlight(pos(2, 2)).highlight(pos(0, 0));
+  assert_eq!(diagram.to_string(), ADJACENT_HIGHLIGHTS);
+  assert_eq!(Papamu::from_diagram(ADJACENT_HIGHLIGHTS).expect("valid diagram"), papamu);
+}
+
+#[test]
+fn move_highlights() {
+  let mut game = Game::from_diagram(BEFORE_JUMP).expect("valid diagram");
+  let mv: Move = "b0-d0".parse().expect("valid move");
+  game.next_turn(mv).expect("legal jump");
+  assert_eq!(game.papamu().diagram().highlight_move(mv).to_string(), AFTER_JUMP);
+  assert_eq!(Papamu::from_diagram(AFTER_JUMP).expect("valid diagram"), *game.papamu());
+}
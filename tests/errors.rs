@@ -0,0 +1,111 @@
+// errors.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// `Error::code` and `Error::name` are stable identifiers: every variant keeps the code and name it was
+// given, none is shared, and with `--features c-api` the C `KonaneError` values match the codes.
+
+extern crate konane;
+
+use std::io;
+
+use konane::{Direction, Occupancy, Position};
+use konane::errors::Error;
+
+fn pos(text: &str) -> Position { text.parse().expect("valid position") }
+
+// One of every variant, in code order.
+fn every_error() -> Vec<Error> {
+  vec![Error::IllegalTarget {
+         source_occ: Occupancy::Black,
+         source_pos: pos("a0"),
+         target_pos: pos("b1"),
+       },
+       Error::OccupiedTarget { target_pos: pos("c0") },
+       Error::EmptySource { source_pos: pos("a0") },
+       Error::WrongColor {
+         source_occ: Occupancy::White,
+         source_pos: pos("b0"),
+         desired_occ: Occupancy::Black,
+       },
+       Error::NoTargets { source_occ: Occupancy::Black, source_pos: pos("a0") },
+       Error::IllegalJump {
+         source_occ: Occupancy::Black,
+         source_pos: pos("a0"),
+         mid_occ: Occupancy::Empty,
+         mid_pos: pos("b0"),
+         target_pos: pos("c0"),
+       },
+       Error::IllegalRemoval { source_occ: Occupancy::Black, source_pos: pos("b1") },
+       Error::OpeningComplete { source_pos: pos("a0") },
+       Error::OpeningIncomplete { source_pos: pos("a0") },
+       Error::TurningJump {
+         source_occ: Occupancy::Black,
+         source_pos: pos("a0"),
+         turn_pos: pos("c0"),
+       },
+       Error::OffBoardJump {
+         source_occ: Occupancy::Black,
+         source_pos: pos("a0"),
+         direction: Direction::Left,
+       },
+       Error::IllegalPass { player: Occupancy::Black },
+       Error::LongJumpPath { source_pos: pos("a0") },
+       Error::GameOver { winner: Occupancy::White, plies: 5 },
+       Error::BadNotation {
+         text: "z9-".to_owned(),
+         column: 4,
+         expected: "a position",
+       },
+       Error::MisplacedPiece { occ: Occupancy::White, pos: pos("a0") },
+       Error::BadBoardSize { width: 0, height: 4 },
+       Error::OffBoard { pos: pos("e0"), width: 4, height: 4 },
+       Error::EmptyTurn,
+       Error::BadDiagram { line: 1, column: 1, expected: "a board diagram" },
+       Error::BadKgn { line: 2, column: 3, message: "unterminated tag".to_owned() },
+       Error::Io { inner: io::Error::new(io::ErrorKind::UnexpectedEof, "short read") },
+       Error::NoSuchPly { ply: 3, plies: 2 },
+       Error::BadTablebase { expected: "a tablebase header" }]
+}
+
+#[test]
+fn codes_are_stable_and_unique() {
+  let codes: Vec<u32> = every_error().iter().map(Error::code).collect();
+  assert_eq!(codes, (1..25).collect::<Vec<u32>>());
+}
+
+#[test]
+fn names_are_stable_and_unique() {
+  let names: Vec<&str> = every_error().iter().map(Error::name).collect();
+  assert_eq!(names,
+             vec!["illegal-target", "occupied-target", "empty-source", "wrong-color", "no-targets",
+                  "illegal-jump", "illegal-removal", "opening-complete", "opening-incomplete", "turning-jump",
+                  "off-board-jump", "illegal-pass", "long-jump-path", "game-over", "bad-notation",
+                  "misplaced-piece", "bad-board-size", "off-board", "empty-turn", "bad-diagram", "bad-kgn",
+                  "io", "no-such-ply", "bad-tablebase"]);
+  let mut unique = names.clone();
+  unique.sort();
+  unique.dedup();
+  assert_eq!(unique.len(), names.len());
+}
+
+#[cfg(feature = "c-api")]
+#[test]
+fn c_errors_match_codes() {
+  for err in every_error() {
+    assert_eq!(konane::KonaneError::from(&err) as u32, err.code(), "{}", err.name());
+  }
+  assert_eq!(konane::KonaneError::KonaneErrorNone as u32, 0);
+}
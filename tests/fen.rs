@@ -0,0 +1,99 @@
+// fen.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate konane;
+
+use konane::{Game, RuleSet};
+use konane::errors::Error;
+
+fn check_round_trip(game: &Game) {
+  let fen = game.to_fen();
+  let read = Game::from_fen(&fen).unwrap_or_else(|err| panic!("{}\n{}", err, fen));
+  assert_eq!(read, *game, "{}", fen);
+  assert_eq!(read.to_fen(), fen);
+}
+
+#[test]
+fn starting_position() {
+  let game = Game::new_black_sized(4, 4).expect("valid board size");
+  assert_eq!(game.to_fen(), "wbwb/bwbw/wbwb/bwbw b 4x4 0");
+  check_round_trip(&game);
+}
+
+#[test]
+fn after_the_opening() {
+  let mut game = Game::new_black_sized(6, 6).expect("valid board size");
+  for mv in &["x a0", "x b0", "a2-a0"] {
+    game.next_turn(mv.parse().expect("valid move")).expect("legal move");
+  }
+  assert_eq!(game.to_fen(), "wbwbwb/bwbwbw/wbwbwb/1wbwbw/1bwbwb/b1bwbw w 6x6 3");
+  check_round_trip(&game);
+}
+
+#[test]
+fn random_games() {
+  for &(width, height) in &[(26, 26), (8, 5), (1, 26), (26, 3)] {
+    for seed in 1..6 {
+      let start = Game::new_black_sized(width, height).expect("valid board size");
+      let playout = start.seeded_playout([seed, 4, 5, 6], usize::max_value());
+      let mut game = start;
+      check_round_trip(&game);
+      for &mv in &playout.moves {
+        game.next_turn(mv).expect("legal move");
+        check_round_trip(&game);
+      }
+    }
+  }
+}
+
+#[test]
+fn missing_plies_default_to_zero() {
+  let game = Game::from_fen("wbwb/bwbw/wbwb/bwbw b 4x4").expect("valid position");
+  assert_eq!(game, Game::new_black_sized(4, 4).expect("valid board size"));
+}
+
+#[test]
+fn rulesets() {
+  for &(name, rules) in RuleSet::presets() {
+    let start = Game::new_black_sized(6, 6).expect("valid board size").with_rules(rules);
+    let mut game = start;
+    for &mv in &start.seeded_playout([9, 9, 9, 9], 5).moves {
+      game.next_turn(mv).expect("legal move");
+    }
+    let fen = game.to_fen();
+    if rules == RuleSet::standard() {
+      assert_eq!(fen.split(' ').count(), 4, "{}", fen);
+    } else {
+      assert!(fen.ends_with(&format!(" 5 {}", name)), "{}", fen);
+    }
+    check_round_trip(&game);
+  }
+  let custom = RuleSet { passing: true, ..RuleSet::corner_only() };
+  let game = Game::new_black_sized(4, 4).expect("valid board size").with_rules(custom);
+  assert_eq!(game.to_fen(), "wbwb/bwbw/wbwb/bwbw b 4x4 0 corner+passing");
+  check_round_trip(&game);
+}
+
+#[test]
+fn bad_rulesets() {
+  match Game::from_fen("wbwb/bwbw/wbwb/bwbw b 4x4 0 sideways") {
+    Err(Error::BadNotation { column: 29, .. }) => {},
+    other => panic!("expected BadNotation, got {:?}", other),
+  }
+  match Game::from_fen("wbwb/bwbw/wbwb/bwbw b 4x4 0 ") {
+    Err(Error::BadNotation { column: 29, .. }) => {},
+    other => panic!("expected BadNotation, got {:?}", other),
+  }
+}
@@ -0,0 +1,116 @@
+// jumps.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// Jumps run in a straight line: a piece may jump several times in one move, but only in the direction
+// it started in.
+
+extern crate konane;
+
+use konane::{Direction, Game, Move, Occupancy, Position};
+use konane::errors::Error;
+
+const POSITION: &'static str = " 5 . . . . . .
+ 4 x o . . . .
+ 3 o . o . . .
+ 2 x o . o . .
+ 1 . . . . . .
+ 0 . . . . . .
+   A B C D E F
+black to move";
+
+fn position() -> Game { Game::from_diagram(POSITION).expect("valid diagram") }
+
+fn pos(text: &str) -> Position { text.parse().expect("valid position") }
+
+fn parse(mv: &str) -> Move { mv.parse().expect("valid move") }
+
+fn rejected(mv: Move) -> Error {
+  let mut game = position();
+  let error = game.next_turn(mv).expect_err("illegal move");
+  assert_eq!(game, position());
+  error
+}
+
+#[test]
+fn straight_jumps() {
+  let game = position();
+  let moves: Vec<String> = game.legal_moves().map(|mv| mv.to_string()).collect();
+  assert_eq!(moves, vec!["a2-c2", "a2-c2-e2", "a4-c4"]);
+
+  let mut next = game;
+  next.next_turn(Move::jump(pos("a2"), Direction::Right, 2)).expect("legal double jump");
+  for &empty in &["a2", "b2", "c2", "d2"] {
+    assert!(next.papamu()[pos(empty)].is_empty(), "{}", empty);
+  }
+  assert_eq!(next.papamu()[pos("e2")], Occupancy::Black);
+  assert_eq!(next.current_player(), Occupancy::White);
+  assert_eq!(next.plies(), 1);
+}
+
+#[test]
+fn paths_and_jumps_agree() {
+  let mut by_path = position();
+  by_path.next_turn(parse("a2-c2-e2")).expect("legal double jump");
+  let mut by_jump = position();
+  by_jump.next_turn(Move::jump(pos("a2"), Direction::Right, 2)).expect("legal double jump");
+  assert_eq!(by_path, by_jump);
+}
+
+#[test]
+fn turning_jumps() {
+  match rejected(parse("a2-c2-c4")) {
+    Error::TurningJump { source_occ: Occupancy::Black, source_pos, turn_pos } => {
+      assert_eq!(source_pos, pos("a2"));
+      assert_eq!(turn_pos, pos("c2"));
+    },
+    other => panic!("expected TurningJump, got {:?}", other),
+  }
+  match position().jump_chain(pos("a2"), &[pos("c2"), pos("c4")]) {
+    Err(Error::TurningJump { turn_pos, .. }) => assert_eq!(turn_pos, pos("c2")),
+    other => panic!("expected TurningJump, got {:?}", other),
+  }
+}
+
+#[test]
+fn bad_jumps() {
+  match rejected(Move::jump(pos("a2"), Direction::Down, 1)) {
+    Error::IllegalJump { mid_occ: Occupancy::Empty, mid_pos, target_pos, .. } => {
+      assert_eq!(mid_pos, pos("a1"));
+      assert_eq!(target_pos, pos("a0"));
+    },
+    other => panic!("expected IllegalJump, got {:?}", other),
+  }
+  match rejected(Move::jump(pos("a2"), Direction::Up, 1)) {
+    Error::OccupiedTarget { target_pos } => assert_eq!(target_pos, pos("a4")),
+    other => panic!("expected OccupiedTarget, got {:?}", other),
+  }
+  match rejected(Move::jump(pos("a2"), Direction::Right, 3)) {
+    Error::OffBoardJump { source_pos, direction: Direction::Right, .. } => assert_eq!(source_pos, pos("e2")),
+    other => panic!("expected OffBoardJump, got {:?}", other),
+  }
+  match rejected(Move::jump(pos("a2"), Direction::Left, 1)) {
+    Error::OffBoardJump { source_pos, direction: Direction::Left, .. } => assert_eq!(source_pos, pos("a2")),
+    other => panic!("expected OffBoardJump, got {:?}", other),
+  }
+  match rejected(Move::jump(pos("b2"), Direction::Right, 1)) {
+    Error::WrongColor { source_occ: Occupancy::White, desired_occ: Occupancy::Black, .. } => {},
+    other => panic!("expected WrongColor, got {:?}", other),
+  }
+  match rejected(Move::jump(pos("c2"), Direction::Right, 1)) {
+    Error::EmptySource { source_pos } => assert_eq!(source_pos, pos("c2")),
+    other => panic!("expected EmptySource, got {:?}", other),
+  }
+}
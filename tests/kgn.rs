@@ -0,0 +1,124 @@
+// kgn.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Game, GameRecord};
+use konane::errors::Error;
+use konane::kgn::{self, KgnGame};
+use rand::{SeedableRng, XorShiftRng};
+
+fn random_game(seed: u32, size: u8) -> KgnGame {
+  let start = Game::new_black_sized(size, size).expect("valid board size");
+  let playout = start.seeded_playout([seed, 1, 2, 3], usize::max_value());
+  let mut record = GameRecord::new(start);
+  for &mv in &playout.moves {
+    record.push(mv).expect("legal move");
+  }
+  KgnGame::new(record)
+}
+
+#[test]
+fn round_trip_on_the_largest_board() {
+  for seed in 1..21 {
+    let mut game = random_game(seed, 26);
+    game.set_tag("Event", "round trip");
+    let plies = game.record().len();
+    game.add_comment(0, "opening").expect("ply in the record");
+    game.add_comment(plies, "final position").expect("ply in the record");
+    let text = game.to_kgn();
+    let read: KgnGame = text.parse().unwrap_or_else(|err| panic!("{}\n{}", err, text));
+    assert_eq!(read.record().moves(), game.record().moves());
+    assert_eq!(read.tag("Event"), Some("round trip"));
+    assert_eq!(read.comments(), game.comments());
+    assert_eq!(read.to_kgn(), text);
+  }
+}
+
+#[test]
+fn several_games_in_one_file() {
+  let games: Vec<KgnGame> = (1..6).map(|seed| random_game(seed, 8)).collect();
+  let mut out = Vec::new();
+  kgn::write_games(&mut out, &games).expect("writing to memory");
+  let read: Vec<KgnGame> = kgn::read_games(&out[..]).collect::<Result<_, _>>().expect("valid KGN");
+  assert_eq!(read.len(), games.len());
+  for (read, game) in read.iter().zip(&games) {
+    assert_eq!(read.record().moves(), game.record().moves());
+  }
+}
+
+#[test]
+fn comment_past_the_end() {
+  let mut game = random_game(7, 6);
+  let plies = game.record().len();
+  match game.add_comment(plies + 1, "too late") {
+    Err(Error::NoSuchPly { ply, plies: recorded }) => assert_eq!((ply, recorded), (plies + 1, plies)),
+    other => panic!("expected NoSuchPly, got {:?}", other),
+  }
+  assert!(game.comments().is_empty());
+}
+
+#[test]
+fn control_characters_in_tags() {
+  let mut game = random_game(3, 6);
+  let value = "two\nlines\r\tand \u{1b}[1m \"quoted\" \\ kōnane";
+  game.set_tag("Annotator", value);
+  let text = game.to_kgn();
+  let escaped = r#"[Annotator "two\nlines\r\tand \u{1b}[1m \"quoted\" \\ kōnane"]"#;
+  assert!(text.lines().any(|line| line == escaped), "{}", text);
+  let read: KgnGame = text.parse().unwrap_or_else(|err| panic!("{}\n{}", err, text));
+  assert_eq!(read.tag("Annotator"), Some(value));
+  assert_eq!(read.to_kgn(), text);
+}
+
+#[test]
+fn declared_result_of_an_unfinished_game() {
+  let text = "[Size \"6x6\"]\n[Result \"W\"]\n\n1. x a0 x a1 2. c0-a0 {black resigns} W\n";
+  let game: KgnGame = text.parse().expect("valid KGN");
+  assert_eq!(game.record().len(), 3);
+  assert!(game.record().current().outcome().is_none());
+  assert_eq!(game.result(), "W");
+  let written = game.to_kgn();
+  assert!(written.contains("[Result \"W\"]"), "{}", written);
+  assert!(written.trim_right().ends_with("{black resigns} W"), "{}", written);
+  let read: KgnGame = written.parse().expect("valid KGN");
+  assert_eq!(read.result(), "W");
+
+  let adjudicated: KgnGame = "[Size \"6x6\"]\n\n1. x a0 x a1 B\n".parse().expect("valid KGN");
+  assert_eq!(adjudicated.result(), "B");
+  let unknown: KgnGame = "[Size \"6x6\"]\n\n1. x a0 x a1 *\n".parse().expect("valid KGN");
+  assert_eq!(unknown.result(), "*");
+}
+
+fn error_position(text: &str) -> (usize, usize) {
+  match text.parse::<KgnGame>() {
+    Err(Error::BadKgn { line, column, .. }) => (line, column),
+    other => panic!("expected BadKgn for {:?}, got {:?}", text, other),
+  }
+}
+
+#[test]
+fn error_positions() {
+  assert_eq!(error_position("[Event \"unclosed]\n\n*\n"), (1, 18));
+  assert_eq!(error_position("[Event \"bad \\q escape\"]\n\n*\n"), (1, 14));
+  assert_eq!(error_position("[Event \"bad \\u{110000}\"]\n\n*\n"), (1, 13));
+  assert_eq!(error_position("[Event \"?\"]\n[Size \"6by6\"]\n\n*\n"), (2, 1));
+  assert_eq!(error_position("[Size \"6x6\"]\n\n1. x a0 x b1 *\n"), (3, 9));
+  assert_eq!(error_position("[Size \"6x6\"]\n\n1. x a0\n   x a1 c0-a1 *\n"), (4, 9));
+  assert_eq!(error_position("[Size \"6x6\"]\n\n1. x a0 } *\n"), (3, 9));
+  assert_eq!(error_position("[Size \"6x6\"]\n\n{never closed\n1. x a0 *\n"), (3, 1));
+  assert_eq!(error_position("[Size \"6x6\"]\n\n1. x a0 x a1 1-0\n"), (3, 14));
+}
@@ -0,0 +1,93 @@
+// misere.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// Under misère rules the player left without a move wins; `Game::outcome` and the solver both follow it.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Game, Move, Occupancy, Outcome, RuleSet};
+use konane::errors::Error;
+use rand::{SeedableRng, XorShiftRng};
+
+// Black, to move, has no jump.
+const BLACK_STUCK: &'static str = " 1 o x .
+ 0 x . x
+   A B C
+black to move";
+
+// Whether the player to move wins, trying every move.
+fn wins_by_search(game: &Game) -> bool {
+  let moves: Vec<Move> = game.legal_moves().collect();
+  if moves.is_empty() {
+    return game.outcome().map_or(false, |outcome| outcome.winner == game.current_player());
+  }
+  moves.into_iter().any(|mv| {
+    let mut child = *game;
+    child.next_turn(mv).expect("legal move");
+    !wins_by_search(&child)
+  })
+}
+
+#[test]
+fn the_stuck_player_wins() {
+  let standard = Game::from_diagram(BLACK_STUCK).expect("valid diagram");
+  let mut misere = standard.with_rules(RuleSet::misere());
+  assert_eq!(standard.outcome(), Some(Outcome { winner: Occupancy::White, plies: 0 }));
+  assert_eq!(misere.outcome(), Some(Outcome { winner: Occupancy::Black, plies: 0 }));
+  match misere.next_turn("a0-c0".parse().expect("valid move")) {
+    Err(Error::GameOver { winner: Occupancy::Black, plies: 0 }) => {},
+    other => panic!("expected GameOver, got {:?}", other),
+  }
+}
+
+#[test]
+fn outcomes_of_random_games() {
+  let mut rng = XorShiftRng::from_seed([17, 17, 17, 17]);
+  for _ in 0..20 {
+    let start = Game::new_black_sized(6, 6).expect("valid board size");
+    let end = start.random_playout(&mut rng).end;
+    let standard = end.outcome().expect("finished game");
+    let misere = end.with_rules(RuleSet::misere()).outcome().expect("finished game");
+    assert_eq!(misere.winner, end.current_player());
+    assert!(standard.winner != misere.winner);
+    assert_eq!(standard.plies, misere.plies);
+  }
+}
+
+#[test]
+fn solutions() {
+  for &(width, height) in &[(4, 4), (4, 5), (5, 4), (3, 6)] {
+    let game = Game::new_black_sized(width, height).expect("valid board size").with_rules(RuleSet::misere());
+    let solution = game.solve();
+    assert_eq!(solution.winner == game.current_player(), wins_by_search(&game), "{}x{}", width, height);
+    let mut end = game;
+    for &mv in &solution.principal_variation {
+      end.next_turn(mv).expect("legal move");
+    }
+    assert_eq!(end.outcome().map(|outcome| outcome.winner), Some(solution.winner));
+    // The last player able to move loses.
+    assert_eq!(solution.winner, end.current_player());
+  }
+}
+
+#[test]
+fn misere_changes_the_winner() {
+  // Black wins 5x5 under the standard rules.
+  let game = Game::new_black_sized(5, 5).expect("valid board size");
+  assert_eq!(game.solve().winner, Occupancy::Black);
+  assert_eq!(game.with_rules(RuleSet::misere()).solve().winner, Occupancy::White);
+}
@@ -0,0 +1,92 @@
+// moves.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// `legal_moves` lists every move `next_turn` accepts, each exactly once, in a fixed order: removals and
+// jumps by source square, files first, then by direction and by number of jumps.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Direction, Game, Move, Position};
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+// Tries every removal and every straight jump from every square, in `legal_moves` order.
+fn accepted_moves(game: &Game) -> Vec<Move> {
+  let (width, height) = (game.papamu().width(), game.papamu().height());
+  let mut moves = Vec::new();
+  for x in 0..width {
+    for y in 0..height {
+      let source = Position::new(x, y).expect("position on the board");
+      let mut candidates = vec![Move::remove(source)];
+      for &direction in &DIRECTIONS {
+        for jumps in 1..(width.max(height) / 2 + 1) {
+          candidates.push(Move::jump(source, direction, jumps));
+        }
+      }
+      for mv in candidates {
+        let mut next = *game;
+        if next.next_turn(mv).is_ok() {
+          moves.push(mv);
+        }
+      }
+    }
+  }
+  moves
+}
+
+#[test]
+fn legal_moves_are_exactly_the_accepted_moves() {
+  let mut rng = XorShiftRng::from_seed([3, 1, 4, 1]);
+  for &(width, height) in &[(4, 4), (6, 6), (5, 7), (8, 8), (1, 9), (12, 3)] {
+    for _ in 0..5 {
+      let mut game = Game::new_black_sized(width, height).expect("valid board size");
+      loop {
+        let moves: Vec<Move> = game.legal_moves().collect();
+        assert_eq!(moves, accepted_moves(&game), "{}x{} after {} plies", width, height, game.plies());
+        if moves.is_empty() {
+          break;
+        }
+        game.next_turn(moves[rng.gen_range(0, moves.len())]).expect("legal move");
+      }
+    }
+  }
+}
+
+#[test]
+fn every_jump_length() {
+  let game = Game::from_diagram(" 0 x o . o . o .
+   A B C D E F G
+black to move")
+    .expect("valid diagram");
+  let moves: Vec<String> = game.legal_moves().map(|mv| mv.to_string()).collect();
+  assert_eq!(moves, vec!["a0-c0", "a0-c0-e0", "a0-c0-e0-g0"]);
+  assert_eq!(game.legal_moves().map(|mv| mv.jumps()).collect::<Vec<_>>(), vec![1, 2, 3]);
+}
+
+#[test]
+fn order_is_repeatable() {
+  let mut game = Game::new_black_sized(6, 6).expect("valid board size");
+  for mv in &["x c2", "x c3", "a2-c2"] {
+    game.next_turn(mv.parse().expect("valid move")).expect("legal move");
+  }
+  let first: Vec<Move> = game.legal_moves().collect();
+  let copy = game;
+  assert_eq!(copy.legal_moves().collect::<Vec<_>>(), first);
+  let moves: Vec<String> = first.iter().map(|mv| mv.to_string()).collect();
+  assert_eq!(moves, vec!["a3-c3", "b0-b2", "b4-b2", "c1-c3", "c5-c3", "d2-b2", "e3-c3"]);
+}
@@ -0,0 +1,70 @@
+// notation.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Every move must read back from its own `Display` text, including jumps from the files x, y and z,
+// which share their letter with the removal marker.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Direction, Game, Move, Position};
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+fn pos(x: u8, y: u8) -> Position { Position::new(x, y).expect("position on the board") }
+
+fn check_round_trip(mv: Move) {
+  let text = mv.to_string();
+  let parsed: Move = text.parse().unwrap_or_else(|err| panic!("cannot parse {:?}: {}", text, err));
+  assert_eq!(parsed, mv, "{}", text);
+  assert_eq!(parsed.to_string(), text);
+}
+
+#[test]
+fn jumps_from_the_last_files() {
+  for x in 23..26 {
+    check_round_trip(Move::jump(pos(x, 25), Direction::Left, 1));
+    check_round_trip(Move::jump(pos(x, 4), Direction::Left, 3));
+    check_round_trip(Move::jump(pos(x, 0), Direction::Up, 2));
+  }
+  let from_x = |text: &str| text.parse::<Move>().expect("jump from file x");
+  assert_eq!(from_x("x25-z25"), Move::jump(pos(23, 25), Direction::Right, 1));
+  assert_eq!(from_x("x3-x5"), Move::jump(pos(23, 3), Direction::Up, 1));
+}
+
+#[test]
+fn removals() {
+  for &(x, y) in &[(0, 0), (4, 5), (23, 25), (25, 25)] {
+    check_round_trip(Move::remove(pos(x, y)));
+  }
+  assert_eq!("x e5".parse::<Move>().expect("removal"), Move::remove(pos(4, 5)));
+  assert_eq!("X  x25".parse::<Move>().expect("removal"), Move::remove(pos(23, 25)));
+}
+
+#[test]
+fn random_moves_on_the_largest_board() {
+  let mut rng = XorShiftRng::from_seed([26, 26, 26, 26]);
+  for _ in 0..10 {
+    let mut game = Game::new_black_sized(26, 26).expect("valid board size");
+    loop {
+      let moves: Vec<Move> = game.legal_moves().collect();
+      if moves.is_empty() {
+        break;
+      }
+      let mv = moves[rng.gen_range(0, moves.len())];
+      check_round_trip(mv);
+      game.next_turn(mv).expect("legal move");
+    }
+  }
+}
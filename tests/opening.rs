@@ -0,0 +1,105 @@
+// opening.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// The opening: black removes one of its pieces from the centre or a corner, then white removes one of its
+// pieces next to the gap, and only then do jumps begin.
+
+extern crate konane;
+
+use konane::{Game, Move, Occupancy, Position};
+use konane::errors::Error;
+
+fn new_game() -> Game { Game::new_black_sized(6, 6).expect("valid board size") }
+
+fn parse(mv: &str) -> Move { mv.parse().expect("valid move") }
+
+fn pos(text: &str) -> Position { text.parse().expect("valid position") }
+
+fn removals(game: &Game) -> Vec<String> { game.legal_moves().map(|mv| mv.to_string()).collect() }
+
+#[test]
+fn black_removes_from_the_centre_or_a_corner() {
+  let game = new_game();
+  assert!(game.is_opening());
+  assert_eq!(removals(&game), vec!["x a0", "x c2", "x d3", "x f5"]);
+  for &target in &["a0", "c2", "d3", "f5"] {
+    let mut next = game;
+    next.next_turn(Move::remove(pos(target))).expect("legal removal");
+    assert_eq!(next.current_player(), Occupancy::White);
+    assert!(next.papamu()[pos(target)].is_empty());
+  }
+}
+
+#[test]
+fn white_removes_next_to_the_gap() {
+  let mut game = new_game();
+  game.next_turn(parse("x c2")).expect("legal removal");
+  assert!(game.is_opening());
+  assert_eq!(removals(&game), vec!["x b2", "x c1", "x c3", "x d2"]);
+  game.next_turn(parse("x c3")).expect("legal removal");
+  assert!(!game.is_opening());
+  assert_eq!(game.current_player(), Occupancy::Black);
+  assert!(game.legal_moves().all(|mv| mv.is_jump()));
+}
+
+#[test]
+fn bad_first_removals() {
+  let game = new_game();
+  let mut next = game;
+  match next.next_turn(parse("x b1")) {
+    Err(Error::IllegalRemoval { source_occ: Occupancy::Black, source_pos }) => {
+      assert_eq!(source_pos, pos("b1"))
+    },
+    other => panic!("expected IllegalRemoval, got {:?}", other),
+  }
+  match next.next_turn(parse("x b0")) {
+    Err(Error::WrongColor { source_occ: Occupancy::White, desired_occ: Occupancy::Black, .. }) => {},
+    other => panic!("expected WrongColor, got {:?}", other),
+  }
+  assert_eq!(next, game);
+}
+
+#[test]
+fn bad_second_removals() {
+  let mut game = new_game();
+  game.next_turn(parse("x a0")).expect("legal removal");
+  match game.next_turn(parse("x a0")) {
+    Err(Error::EmptySource { source_pos }) => assert_eq!(source_pos, pos("a0")),
+    other => panic!("expected EmptySource, got {:?}", other),
+  }
+  match game.next_turn(parse("x d0")) {
+    Err(Error::IllegalRemoval { source_occ: Occupancy::White, source_pos }) => {
+      assert_eq!(source_pos, pos("d0"))
+    },
+    other => panic!("expected IllegalRemoval, got {:?}", other),
+  }
+}
+
+#[test]
+fn no_jumps_during_the_opening_and_no_removals_after_it() {
+  let mut game = new_game();
+  match game.next_turn(parse("a2-a0")) {
+    Err(Error::OpeningIncomplete { source_pos }) => assert_eq!(source_pos, pos("a2")),
+    other => panic!("expected OpeningIncomplete, got {:?}", other),
+  }
+  game.next_turn(parse("x a0")).expect("legal removal");
+  game.next_turn(parse("x a1")).expect("legal removal");
+  match game.next_turn(parse("x c0")) {
+    Err(Error::OpeningComplete { source_pos }) => assert_eq!(source_pos, pos("c0")),
+    other => panic!("expected OpeningComplete, got {:?}", other),
+  }
+  game.next_turn(parse("c0-a0")).expect("legal jump");
+}
@@ -0,0 +1,85 @@
+// outcome.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// The player left without a move loses; once that happens every move is refused with `GameOver`.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Game, Move, Occupancy, Outcome};
+use konane::errors::Error;
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+#[test]
+fn no_outcome_while_moves_remain() {
+  let mut game = Game::new_black_sized(6, 6).expect("valid board size");
+  assert_eq!(game.outcome(), None);
+  assert!(!game.is_over());
+  game.next_turn("x a0".parse().expect("valid move")).expect("legal move");
+  assert_eq!(game.outcome(), None);
+}
+
+#[test]
+fn last_mover_wins() {
+  let mut rng = XorShiftRng::from_seed([4, 4, 4, 4]);
+  for _ in 0..20 {
+    let mut game = Game::new_black_sized(6, 6).expect("valid board size");
+    let mut last_mover = None;
+    loop {
+      let moves: Vec<Move> = game.legal_moves().collect();
+      if moves.is_empty() {
+        break;
+      }
+      assert_eq!(game.outcome(), None);
+      last_mover = Some(game.current_player());
+      game.next_turn(moves[rng.gen_range(0, moves.len())]).expect("legal move");
+    }
+    let outcome = game.outcome().expect("finished game");
+    assert!(game.is_over());
+    assert_eq!(Some(outcome.winner), last_mover);
+    assert!(outcome.winner != game.current_player());
+    assert_eq!(outcome.plies, game.plies());
+  }
+}
+
+#[test]
+fn stuck_from_the_start() {
+  let game = Game::from_diagram(" 1 o x .
+ 0 x . x
+   A B C
+black to move")
+    .expect("valid diagram");
+  assert_eq!(game.outcome(), Some(Outcome { winner: Occupancy::White, plies: 0 }));
+}
+
+#[test]
+fn moves_after_the_end() {
+  let mut game = Game::from_diagram(" 1 o . . .
+ 0 x o . .
+   A B C D
+black to move")
+    .expect("valid diagram");
+  game.next_turn("a0-c0".parse().expect("valid move")).expect("legal move");
+  assert_eq!(game.outcome(), Some(Outcome { winner: Occupancy::Black, plies: 1 }));
+  let before = game;
+  for mv in &["a1-c1", "c0-a0", "x a1"] {
+    match game.next_turn(mv.parse().expect("valid move")) {
+      Err(Error::GameOver { winner: Occupancy::Black, plies: 1 }) => {},
+      other => panic!("expected GameOver after {}, got {:?}", mv, other),
+    }
+    assert_eq!(game, before);
+  }
+}
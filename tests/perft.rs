@@ -0,0 +1,81 @@
+// perft.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Node counts for the standard rules from the starting position with black to move, checked against an
+// independent move generator.  The deepest counts take a while in debug builds, so they only run with
+// `cargo test -- --ignored`.
+
+extern crate konane;
+
+use konane::Game;
+
+const PERFT_6X6: [u64; 10] = [1, 4, 12, 28, 156, 668, 4192, 22676, 162288, 1030412];
+const PERFT_8X8: [u64; 10] = [1, 4, 12, 28, 172, 892, 7124, 52044, 508088, 4633660];
+const PERFT_10X10: [u64; 10] = [1, 4, 12, 28, 172, 984, 8596, 71380, 791404, 8396548];
+
+const QUICK_DEPTH: usize = 7;
+
+fn check_perft(size: u8, expected: &[u64]) {
+  let game = Game::new_black_sized(size, size).expect("valid board size");
+  for (depth, &count) in expected.iter().enumerate() {
+    assert_eq!(game.perft(depth as u32), count, "perft({}) on {}x{}", depth, size, size);
+  }
+}
+
+#[test]
+fn perft_6x6() { check_perft(6, &PERFT_6X6[..QUICK_DEPTH + 1]); }
+
+#[test]
+fn perft_8x8() { check_perft(8, &PERFT_8X8[..QUICK_DEPTH + 1]); }
+
+#[test]
+fn perft_10x10() { check_perft(10, &PERFT_10X10[..QUICK_DEPTH + 1]); }
+
+#[test]
+#[ignore]
+fn perft_6x6_deep() { check_perft(6, &PERFT_6X6); }
+
+#[test]
+#[ignore]
+fn perft_8x8_deep() { check_perft(8, &PERFT_8X8); }
+
+#[test]
+#[ignore]
+fn perft_10x10_deep() { check_perft(10, &PERFT_10X10); }
+
+#[test]
+fn divide_sums_to_perft() {
+  for &size in &[6, 8, 10] {
+    let game = Game::new_black_sized(size, size).expect("valid board size");
+    for depth in 1..6 {
+      let divided = game.divide(depth);
+      assert_eq!(divided.len(), game.legal_moves().count());
+      assert_eq!(divided.iter().map(|&(_, count)| count).sum::<u64>(), game.perft(depth));
+    }
+  }
+}
+
+#[test]
+fn divide_at_the_root() {
+  let game = Game::new_black_sized(6, 6).expect("valid board size");
+  let mut divided: Vec<(String, u64)> =
+    game.divide(3).into_iter().map(|(mv, count)| (mv.to_string(), count)).collect();
+  divided.sort();
+  assert_eq!(divided,
+             vec![("x a0".to_owned(), 2),
+                  ("x c2".to_owned(), 12),
+                  ("x d3".to_owned(), 12),
+                  ("x f5".to_owned(), 2)]);
+}
@@ -0,0 +1,90 @@
+// playout.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// Playouts are reproducible: the same seed replays the same moves, and every game from `random_games`
+// replays from the seed it comes with.  `max_plies` caps the number of moves.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Game, RuleSet};
+use rand::{SeedableRng, XorShiftRng};
+
+fn start() -> Game { Game::new_black_sized(8, 8).expect("valid board size") }
+
+#[test]
+fn same_seed_same_moves() {
+  for seed in 1..20 {
+    let seed = [seed, 7, 11, 13];
+    let first = start().seeded_playout(seed, usize::max_value());
+    let second = start().seeded_playout(seed, usize::max_value());
+    assert_eq!(first, second);
+    let mut rng = XorShiftRng::from_seed(seed);
+    assert_eq!(start().random_playout(&mut rng), first);
+  }
+  let moves = |seed| start().seeded_playout(seed, usize::max_value()).moves;
+  assert!((2..20).any(|seed| moves([seed, 7, 11, 13]) != moves([1, 7, 11, 13])));
+}
+
+#[test]
+fn random_games_replay_from_their_seeds() {
+  for &(_, rules) in RuleSet::presets() {
+    let start = start().with_rules(rules);
+    let rng = XorShiftRng::from_seed([5, 6, 7, 8]);
+    for (seed, playout) in start.random_games(rng, 30).take(10) {
+      assert_eq!(start.seeded_playout(seed, 30), playout);
+    }
+    let first: Vec<_> = start.random_games(XorShiftRng::from_seed([5, 6, 7, 8]), 30).take(10).collect();
+    let again: Vec<_> = start.random_games(XorShiftRng::from_seed([5, 6, 7, 8]), 30).take(10).collect();
+    assert_eq!(first, again);
+  }
+}
+
+#[test]
+fn playouts_replay_through_next_turn() {
+  for seed in 1..10 {
+    let playout = start().seeded_playout([seed, 2, 3, 4], usize::max_value());
+    assert_eq!(playout.start, start());
+    let mut game = playout.start;
+    for &mv in &playout.moves {
+      game.next_turn(mv).expect("legal move");
+    }
+    assert_eq!(game, playout.end);
+    assert!(game.is_over());
+    assert_eq!(playout.outcome, game.outcome());
+    assert!(playout.outcome.is_some());
+  }
+}
+
+#[test]
+fn max_plies() {
+  for seed in 1..10 {
+    let seed = [seed, 3, 5, 7];
+    let full = start().seeded_playout(seed, usize::max_value());
+    for &max in &[0, 1, 2, 5, full.moves.len() - 1, full.moves.len(), full.moves.len() + 1] {
+      let capped = start().seeded_playout(seed, max);
+      assert!(capped.moves.len() <= max);
+      assert_eq!(capped.moves[..], full.moves[..capped.moves.len()]);
+      if max < full.moves.len() {
+        assert_eq!(capped.moves.len(), max);
+        assert!(!capped.end.is_over());
+        assert_eq!(capped.outcome, None);
+      } else {
+        assert_eq!(capped, full);
+      }
+    }
+  }
+}
@@ -0,0 +1,108 @@
+// record.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// A `GameRecord` keeps every position of a game so that moves can be taken back and replayed; playing a
+// new move anywhere but at the end throws the moves after it away.
+
+extern crate konane;
+
+use konane::{Game, GameRecord, Move};
+use konane::errors::Error;
+
+fn parse(mv: &str) -> Move { mv.parse().expect("valid move") }
+
+fn recorded(moves: &[&str]) -> GameRecord {
+  let mut record = GameRecord::new(Game::new_black_sized(6, 6).expect("valid board size"));
+  for mv in moves {
+    record.push(parse(mv)).expect("legal move");
+  }
+  record
+}
+
+#[test]
+fn undo_and_redo() {
+  let mut record = recorded(&["x a0", "x a1", "c0-a0"]);
+  assert_eq!((record.len(), record.ply()), (3, 3));
+  assert_eq!(record.undo(), Some(parse("c0-a0")));
+  assert_eq!(record.undo(), Some(parse("x a1")));
+  assert_eq!(record.current(), record.position(1).expect("recorded position"));
+  assert_eq!(record.played_moves(), &[parse("x a0")]);
+  assert_eq!(record.redo(), Some(parse("x a1")));
+  assert_eq!(record.ply(), 2);
+  assert_eq!(record.len(), 3);
+}
+
+#[test]
+fn undo_and_redo_at_the_ends() {
+  let mut record = recorded(&[]);
+  assert!(record.is_empty());
+  assert!(!record.can_undo() && !record.can_redo());
+  assert_eq!(record.undo(), None);
+  assert_eq!(record.redo(), None);
+
+  let mut record = recorded(&["x a0", "x b0"]);
+  assert!(!record.can_redo());
+  assert_eq!(record.redo(), None);
+  assert_eq!(record.ply(), 2);
+  assert_eq!(record.undo(), Some(parse("x b0")));
+  assert_eq!(record.undo(), Some(parse("x a0")));
+  assert_eq!(record.undo(), None);
+  assert_eq!(record.ply(), 0);
+  assert_eq!(record.current(), record.start());
+}
+
+#[test]
+fn push_after_undo_cuts_off_the_rest() {
+  let mut record = recorded(&["x a0", "x a1", "c0-a0"]);
+  record.undo();
+  record.undo();
+  record.push(parse("x b0")).expect("legal move");
+  assert_eq!(record.moves(), &[parse("x a0"), parse("x b0")]);
+  assert_eq!((record.len(), record.ply()), (2, 2));
+  assert!(!record.can_redo());
+  assert_eq!(record.positions().count(), 3);
+  let mut replayed = *record.start();
+  for &mv in record.moves() {
+    replayed.next_turn(mv).expect("legal move");
+  }
+  assert_eq!(*record.current(), replayed);
+}
+
+#[test]
+fn rejected_moves_leave_the_record_alone() {
+  let mut record = recorded(&["x a0", "x a1", "c0-a0"]);
+  record.undo();
+  let before = record.clone();
+  assert!(record.push(parse("x c0")).is_err());
+  assert_eq!(record, before);
+  assert!(record.can_redo());
+}
+
+#[test]
+fn go_to() {
+  let mut record = recorded(&["x a0", "x a1", "c0-a0"]);
+  record.go_to(1).expect("recorded ply");
+  assert_eq!(record.current(), record.position(1).expect("recorded position"));
+  record.go_to(3).expect("recorded ply");
+  assert_eq!(record.ply(), 3);
+  match record.go_to(4) {
+    Err(Error::NoSuchPly { ply: 4, plies: 3 }) => {},
+    other => panic!("expected NoSuchPly, got {:?}", other),
+  }
+  assert_eq!(record.ply(), 3);
+  record.go_to(0).expect("recorded ply");
+  assert_eq!(record.current(), record.start());
+}
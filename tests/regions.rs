@@ -0,0 +1,181 @@
+// regions.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// `Papamu::regions` splits a board into pieces that never interact: every region keeps exactly its own
+// pieces, and playing each region out on its own board, in any interleaving, always leaves the full board
+// with just the moves of the regions put together.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Game, Move, Occupancy, Papamu, Position, Region};
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+const PLAYERS: [Occupancy; 2] = [Occupancy::Black, Occupancy::White];
+
+// Two groups at opposite ends of the board, too far apart to ever meet.
+const APART: &'static str = " 9 . . . x o .
+ 8 . . . o . .
+ 7 . . . . . .
+ 6 . . . . . .
+ 5 . . . . . .
+ 4 . . . . . .
+ 3 . . . . . .
+ 2 . . . . . .
+ 1 o x . . . .
+ 0 x o . . . .
+   A B C D E F";
+
+fn squares(papamu: &Papamu) -> Vec<Position> {
+  let mut squares = Vec::new();
+  for x in 0..papamu.width() {
+    for y in 0..papamu.height() {
+      squares.push(Position::new(x, y).expect("position on the board"));
+    }
+  }
+  squares
+}
+
+fn game(papamu: &Papamu, player: Occupancy) -> Game {
+  Game::from_diagram(&format!("{}\n{} to move", papamu, player)).expect("valid diagram")
+}
+
+fn moves(papamu: &Papamu, player: Occupancy) -> Vec<Move> {
+  game(papamu, player).legal_moves().collect()
+}
+
+fn play(papamu: &Papamu, player: Occupancy, mv: Move) -> Papamu {
+  let mut game = game(papamu, player);
+  game.next_turn(mv).expect("legal move");
+  *game.papamu()
+}
+
+// The moves on the full board are exactly the regions' moves, each translated onto it.
+fn check_moves(papamu: &Papamu, regions: &[(Region, Papamu)]) {
+  for &player in &PLAYERS {
+    let mut expected = Vec::new();
+    for &(region, board) in regions {
+      expected.extend(moves(&board, player).into_iter().map(|mv| region.move_to_board(mv).to_string()));
+    }
+    let mut actual: Vec<String> = moves(papamu, player).iter().map(|mv| mv.to_string()).collect();
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected, "{} to move on\n{}", player, papamu);
+  }
+}
+
+// Plays random moves, each in one region on its own board and on the full board, and checks the regions
+// found at the start still account for every move.
+fn check_independent<R: Rng>(papamu: &Papamu, rng: &mut R) {
+  let mut papamu = *papamu;
+  let mut regions: Vec<(Region, Papamu)> = papamu.regions()
+                                                .into_iter()
+                                                .map(|region| (region, *region.papamu()))
+                                                .collect();
+  check_moves(&papamu, &regions);
+  loop {
+    let player = *rng.choose(&PLAYERS).expect("two players");
+    let mut options = Vec::new();
+    for (index, &(_, board)) in regions.iter().enumerate() {
+      options.extend(moves(&board, player).into_iter().map(|mv| (index, mv)));
+    }
+    let (index, mv) = match rng.choose(&options) {
+      Some(&option) => option,
+      None => break,
+    };
+    let region = regions[index].0;
+    papamu = play(&papamu, player, region.move_to_board(mv));
+    regions[index].1 = play(&regions[index].1, player, mv);
+    check_moves(&papamu, &regions);
+  }
+}
+
+fn random_positions() -> Vec<Papamu> {
+  let mut positions = vec![Papamu::from_diagram(APART).expect("valid diagram")];
+  for &(width, height) in &[(8, 8), (10, 6), (3, 12)] {
+    let start = Game::new_black_sized(width, height).expect("valid board size");
+    for seed in 1..8 {
+      let playout = start.seeded_playout([seed, 10, 20, 30], usize::max_value());
+      for plies in 2..playout.moves.len() {
+        let mut game = start;
+        for &mv in &playout.moves[..plies] {
+          game.next_turn(mv).expect("legal move");
+        }
+        positions.push(*game.papamu());
+      }
+    }
+  }
+  positions
+}
+
+#[test]
+fn regions_hold_their_own_pieces() {
+  let mut split = 0;
+  for papamu in random_positions() {
+    let regions = papamu.regions();
+    assert!(!regions.is_empty());
+    if regions.len() > 1 {
+      split += 1;
+    }
+    let mut pieces = Vec::new();
+    for region in &regions {
+      assert!(regions.len() == 1 || !region.papamu().is_opening());
+      for pos in squares(region.papamu()) {
+        let occ = region.papamu()[pos];
+        if occ.is_occupied() {
+          assert_eq!(papamu[region.to_board(pos)], occ);
+          pieces.push(region.to_board(pos));
+        }
+      }
+    }
+    pieces.sort_by_key(|pos| (pos.x(), pos.y()));
+    let all: Vec<Position> = squares(&papamu).into_iter().filter(|&pos| papamu[pos].is_occupied()).collect();
+    assert_eq!(pieces, all, "\n{}", papamu);
+  }
+  assert!(split > 0);
+}
+
+#[test]
+fn regions_are_independent() {
+  let mut rng = XorShiftRng::from_seed([2, 7, 1, 8]);
+  let apart = Papamu::from_diagram(APART).expect("valid diagram");
+  assert_eq!(apart.regions().len(), 2);
+  for papamu in random_positions() {
+    check_independent(&papamu, &mut rng);
+  }
+}
+
+#[test]
+fn board_positions_round_trip() {
+  for papamu in random_positions() {
+    for region in papamu.regions() {
+      let board = region.papamu();
+      assert_eq!(region.to_board(Position::new(0, 0).expect("a0")), region.origin());
+      for pos in squares(board) {
+        assert!(papamu.contains(region.to_board(pos)));
+        assert_eq!(region.from_board(region.to_board(pos)), Some(pos));
+      }
+      let mut count = 0;
+      for pos in squares(&papamu) {
+        if let Some(local) = region.from_board(pos) {
+          assert_eq!(region.to_board(local), pos);
+          count += 1;
+        }
+      }
+      assert_eq!(count, usize::from(board.width()) * usize::from(board.height()));
+    }
+  }
+}
@@ -0,0 +1,197 @@
+// rules.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// Each `RuleSet` preset, how rulesets are written, and the variant rules themselves: where the opening
+// removal may be made, turning multi-jumps and passing.
+
+extern crate konane;
+
+use konane::{Direction, Game, JumpPath, Move, Occupancy, OpeningRule, Position, RuleSet};
+use konane::errors::Error;
+
+fn parse(mv: &str) -> Move { mv.parse().expect("valid move") }
+
+fn pos(text: &str) -> Position { text.parse().expect("valid position") }
+
+fn moves(game: &Game) -> Vec<String> { game.legal_moves().map(|mv| mv.to_string()).collect() }
+
+#[test]
+fn presets() {
+  let names: Vec<&str> = RuleSet::presets().iter().map(|&(name, _)| name).collect();
+  assert_eq!(names, vec!["standard", "centre-only", "corner-only", "turning", "passing", "misere"]);
+  let constructors = [RuleSet::standard(),
+                      RuleSet::centre_only(),
+                      RuleSet::corner_only(),
+                      RuleSet::turning(),
+                      RuleSet::passing(),
+                      RuleSet::misere()];
+  for (&(name, rules), &constructed) in RuleSet::presets().iter().zip(&constructors) {
+    assert_eq!(rules, constructed, "{}", name);
+    assert_eq!(RuleSet::preset(name), Some(rules));
+    assert_eq!(rules.name(), Some(name));
+  }
+  assert_eq!(RuleSet::default(), RuleSet::standard());
+  assert_eq!(RuleSet::standard(),
+             RuleSet {
+               opening: OpeningRule::CentreOrCorner,
+               turning_jumps: false,
+               passing: false,
+               misere: false,
+             });
+  assert_eq!(RuleSet::centre_only().opening, OpeningRule::Centre);
+  assert_eq!(RuleSet::corner_only().opening, OpeningRule::Corner);
+  assert!(RuleSet::turning().turning_jumps);
+  assert!(RuleSet::passing().passing);
+  assert!(RuleSet::misere().misere);
+  assert_eq!(RuleSet::preset("blitz"), None);
+}
+
+#[test]
+fn written_rulesets() {
+  for &opening in &[OpeningRule::CentreOrCorner, OpeningRule::Centre, OpeningRule::Corner,
+                    OpeningRule::Anywhere] {
+    for flags in 0..8 {
+      let rules = RuleSet {
+        opening: opening,
+        turning_jumps: flags & 1 != 0,
+        passing: flags & 2 != 0,
+        misere: flags & 4 != 0,
+      };
+      let text = rules.to_string();
+      assert_eq!(text.parse::<RuleSet>().unwrap_or_else(|err| panic!("{}: {}", text, err)), rules);
+      if let Some(name) = rules.name() {
+        assert_eq!(text, name);
+      }
+    }
+  }
+  assert_eq!(RuleSet::turning().to_string(), "turning");
+  assert_eq!(RuleSet { turning_jumps: true, ..RuleSet::corner_only() }.to_string(), "corner+turning");
+  assert_eq!("centre-or-corner+misere".parse::<RuleSet>().expect("valid ruleset"), RuleSet::misere());
+  for &bad in &["", "sideways", "corner+flying", "corner+turning+turning", "+turning", "Standard"] {
+    match bad.parse::<RuleSet>() {
+      Err(Error::BadNotation { .. }) => {},
+      other => panic!("expected BadNotation for {:?}, got {:?}", bad, other),
+    }
+  }
+}
+
+#[test]
+fn opening_rules() {
+  let opening = |rules: RuleSet| {
+    moves(&Game::new_black_sized(6, 6).expect("valid board size").with_rules(rules))
+  };
+  assert_eq!(opening(RuleSet::standard()), vec!["x a0", "x c2", "x d3", "x f5"]);
+  assert_eq!(opening(RuleSet::centre_only()), vec!["x c2", "x d3"]);
+  assert_eq!(opening(RuleSet::corner_only()), vec!["x a0", "x f5"]);
+  assert_eq!(opening(RuleSet { opening: OpeningRule::Anywhere, ..RuleSet::standard() }).len(), 18);
+  let mut game = Game::new_black_sized(6, 6).expect("valid board size").with_rules(RuleSet::corner_only());
+  match game.next_turn(parse("x c2")) {
+    Err(Error::IllegalRemoval { source_pos, .. }) => assert_eq!(source_pos, pos("c2")),
+    other => panic!("expected IllegalRemoval, got {:?}", other),
+  }
+}
+
+const TURNING: &'static str = " 5 . . . . . .
+ 4 x o . . . .
+ 3 o . o . . .
+ 2 x o . o . .
+ 1 . . . . . .
+ 0 . . . . . .
+   A B C D E F
+black to move";
+
+#[test]
+fn turning_jumps() {
+  let game = Game::from_diagram(TURNING).expect("valid diagram").with_rules(RuleSet::turning());
+  assert_eq!(moves(&game), vec!["a2-c2", "a2-c2-c4", "a2-c2-e2", "a4-c4", "a4-c4-c2", "a4-c4-c2-e2"]);
+  let mut next = game;
+  next.next_turn(parse("a2-c2-c4")).expect("legal turning jump");
+  for &empty in &["a2", "b2", "c2", "c3"] {
+    assert!(next.papamu()[pos(empty)].is_empty(), "{}", empty);
+  }
+  assert_eq!(next.papamu()[pos("c4")], Occupancy::Black);
+  for mv in game.legal_moves() {
+    let mut next = game;
+    next.next_turn(mv).expect("legal move");
+  }
+  let path = JumpPath::from_directions(&[Direction::Right, Direction::Up]).expect("short path");
+  assert_eq!(game.jump_chain(pos("a2"), &[pos("c2"), pos("c4")]).expect("legal chain"),
+             Move::path(pos("a2"), path));
+}
+
+// Black, to move, has no jump while white does.
+const BLACK_STUCK: &'static str = " 1 o x . .
+ 0 . . . .
+   A B C D
+black to move";
+
+#[test]
+fn passing() {
+  let standard = Game::from_diagram(BLACK_STUCK).expect("valid diagram");
+  assert!(standard.is_over());
+  let mut game = standard.with_rules(RuleSet::passing());
+  assert_eq!(game.legal_moves().collect::<Vec<_>>(), vec![Move::Pass]);
+  assert_eq!(game.outcome(), None);
+  game.next_turn(Move::Pass).expect("legal pass");
+  assert_eq!(game.current_player(), Occupancy::White);
+  assert_eq!(game.plies(), 1);
+  match game.next_turn(Move::Pass) {
+    Err(Error::IllegalPass { player: Occupancy::White }) => {},
+    other => panic!("expected IllegalPass, got {:?}", other),
+  }
+  game.next_turn(parse("a1-c1")).expect("legal jump");
+  // Neither side can jump now, so black, to move, loses.
+  assert!(game.legal_moves().next().is_none());
+  assert_eq!(game.outcome().map(|outcome| outcome.winner), Some(Occupancy::White));
+}
+
+#[test]
+fn no_passing_in_the_opening_or_with_a_jump() {
+  let mut game = Game::new_black_sized(6, 6).expect("valid board size").with_rules(RuleSet::passing());
+  match game.next_turn(Move::Pass) {
+    Err(Error::IllegalPass { player: Occupancy::Black }) => {},
+    other => panic!("expected IllegalPass, got {:?}", other),
+  }
+  for mv in &["x a0", "x a1"] {
+    game.next_turn(parse(mv)).expect("legal move");
+  }
+  assert!(game.legal_moves().all(|mv| !mv.is_pass()));
+  match game.next_turn(Move::Pass) {
+    Err(Error::IllegalPass { player: Occupancy::Black }) => {},
+    other => panic!("expected IllegalPass, got {:?}", other),
+  }
+  let mut standard = Game::new_black_sized(6, 6).expect("valid board size");
+  for mv in &["x a0", "x a1"] {
+    standard.next_turn(parse(mv)).expect("legal move");
+  }
+  match standard.next_turn(Move::Pass) {
+    Err(Error::IllegalPass { .. }) => {},
+    other => panic!("expected IllegalPass, got {:?}", other),
+  }
+}
+
+#[test]
+fn notation() {
+  for &(text, mv) in &[("pass", Move::Pass), ("PASS", Move::Pass)] {
+    assert_eq!(text.parse::<Move>().expect("valid move"), mv);
+  }
+  assert_eq!(Move::Pass.to_string(), "pass");
+  let directions = [Direction::Left, Direction::Up, Direction::Left];
+  let turning = Move::path(pos("z3"), JumpPath::from_directions(&directions).expect("short path"));
+  assert_eq!(turning.to_string(), "z3-x3-x5-v5");
+  assert_eq!(turning.to_string().parse::<Move>().expect("valid move"), turning);
+  assert_eq!(parse("a2-c2-e2"), Move::jump(pos("a2"), Direction::Right, 2));
+}
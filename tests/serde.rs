@@ -0,0 +1,90 @@
+// serde.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Round trips through JSON; run with `cargo test --features serde`.
+
+#![cfg(feature = "serde")]
+
+extern crate konane;
+extern crate serde_json;
+
+use konane::{Direction, Game, JumpPath, Move, Papamu, Position, RuleSet};
+
+#[test]
+fn papamu_as_rows() {
+  let papamu = *Game::new_black_sized(3, 2).expect("valid board size").papamu();
+  let json = serde_json::to_string(&papamu).expect("serialisable");
+  assert_eq!(json, r#"{"rows":["wbw","bwb"]}"#);
+  assert_eq!(serde_json::from_str::<Papamu>(&json).expect("valid JSON"), papamu);
+}
+
+#[test]
+fn wrong_colour() {
+  assert!(serde_json::from_str::<Papamu>(r#"{"rows":["bwb","wbw"]}"#).is_err());
+}
+
+#[test]
+fn games_and_moves() {
+  for &(_, rules) in RuleSet::presets() {
+    for seed in 1..4 {
+      let start = Game::new_black_sized(8, 6).expect("valid board size").with_rules(rules);
+      let playout = start.seeded_playout([seed, 7, 8, 9], usize::max_value());
+      let mut game = start;
+      for &mv in &playout.moves {
+        let json = serde_json::to_string(&mv).expect("serialisable");
+        assert_eq!(serde_json::from_str::<Move>(&json).expect("valid JSON"), mv, "{}", json);
+        game.next_turn(mv).expect("legal move");
+        let json = serde_json::to_string(&game).expect("serialisable");
+        assert_eq!(serde_json::from_str::<Game>(&json).expect("valid JSON"), game, "{}", json);
+      }
+    }
+  }
+}
+
+#[test]
+fn rules_default_to_standard() {
+  let json = r#"{"to_move":"White","papamu":{"rows":["wbw",".wb"]},"plies":1}"#;
+  let game: Game = serde_json::from_str(json).expect("valid JSON");
+  assert_eq!(game.rules(), RuleSet::standard());
+  assert_eq!(game.plies(), 1);
+  assert!(game.current_player().is_white());
+}
+
+#[test]
+fn rows_run_top_down() {
+  let mut game = Game::new_black_sized(4, 3).expect("valid board size");
+  game.next_turn("x a0".parse().expect("valid move")).expect("legal move");
+  let json = serde_json::to_string(game.papamu()).expect("serialisable");
+  assert_eq!(json, r#"{"rows":["bwbw","wbwb",".wbw"]}"#);
+}
+
+#[test]
+fn straight_paths_read_back_as_jumps() {
+  let source: Position = "c2".parse().expect("valid position");
+  for &(directions, jumps) in &[(&[Direction::Up][..], 1), (&[Direction::Left, Direction::Left][..], 2)] {
+    let path = JumpPath::from_directions(directions).expect("short path");
+    let raw = Move::Path {
+      source: source,
+      path: path,
+    };
+    let json = serde_json::to_string(&raw).expect("serialisable");
+    let read: Move = serde_json::from_str(&json).expect("valid JSON");
+    assert_eq!(read, Move::jump(source, directions[0], jumps), "{}", json);
+  }
+  let turning_path = JumpPath::from_directions(&[Direction::Up, Direction::Right]).expect("short path");
+  let turning = Move::path(source, turning_path);
+  let json = serde_json::to_string(&turning).expect("serialisable");
+  assert_eq!(serde_json::from_str::<Move>(&json).expect("valid JSON"), turning);
+}
@@ -0,0 +1,98 @@
+// solve.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Solutions of small boards from the starting position with black to move, under the standard rules.  The
+// smallest are checked against a plain search of every line of play.
+
+extern crate konane;
+
+use konane::{Game, Move, Occupancy};
+use konane::solve::{Solution, Solver};
+
+const WINNERS: [(u8, u8, Occupancy); 5] = [(4, 4, Occupancy::White),
+                                           (4, 5, Occupancy::White),
+                                           (5, 4, Occupancy::White),
+                                           (5, 5, Occupancy::Black),
+                                           (6, 4, Occupancy::White)];
+
+fn new_game(width: u8, height: u8) -> Game { Game::new_black_sized(width, height).expect("valid board size") }
+
+// Whether the player to move wins, trying every move.
+fn wins_by_search(game: &Game) -> bool {
+  let moves: Vec<Move> = game.legal_moves().collect();
+  if moves.is_empty() {
+    return game.outcome().map_or(false, |outcome| outcome.winner == game.current_player());
+  }
+  moves.into_iter().any(|mv| {
+    let mut child = *game;
+    child.next_turn(mv).expect("legal move");
+    !wins_by_search(&child)
+  })
+}
+
+fn check_principal_variation(game: &Game, solution: &Solution) {
+  let mut game = *game;
+  for &mv in &solution.principal_variation {
+    game.next_turn(mv).expect("legal move");
+  }
+  assert!(game.is_over());
+  assert_eq!(game.outcome().map(|outcome| outcome.winner), Some(solution.winner));
+}
+
+#[test]
+fn known_winners() {
+  for &(width, height, winner) in &WINNERS {
+    let game = new_game(width, height);
+    let solution = game.solve();
+    assert_eq!(solution.winner, winner, "{}x{}", width, height);
+    check_principal_variation(&game, &solution);
+  }
+}
+
+#[test]
+fn agrees_with_a_full_search() {
+  for &(width, height, winner) in WINNERS.iter().filter(|&&(width, height, _)| width * height <= 20) {
+    let game = new_game(width, height);
+    assert_eq!(wins_by_search(&game), winner == game.current_player(), "{}x{}", width, height);
+  }
+}
+
+#[test]
+fn resumes_after_running_out_of_nodes() {
+  let game = new_game(5, 5);
+  let mut solver = Solver::new().node_limit(100);
+  let mut attempts = 1;
+  let solution = loop {
+    match solver.solve(&game) {
+      Some(solution) => break solution,
+      None => attempts += 1,
+    }
+    assert!(solver.nodes() <= 100);
+  };
+  assert!(attempts > 1);
+  assert_eq!(solution.winner, game.solve().winner);
+  check_principal_variation(&game, &solution);
+}
+
+// Proving 6x6 takes about two minutes in a release build and fills the table with some 7.15 million
+// positions, so it only runs with `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn six_by_six() {
+  let game = new_game(6, 6);
+  let solution = game.solve();
+  assert_eq!(solution.winner, Occupancy::White);
+  check_principal_variation(&game, &solution);
+}
@@ -0,0 +1,128 @@
+// symmetry.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// A symmetry is the same game seen from another angle: it can be undone, it maps legal moves to legal
+// moves and positions to positions, and when it puts pieces on squares of the other colour the pieces and
+// the side to move change colour along with them.
+
+extern crate konane;
+extern crate rand;
+
+use konane::{Game, Move, Position, SYMMETRIES, Symmetry};
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+const SIZES: [(u8, u8); 6] = [(6, 6), (5, 5), (6, 4), (5, 7), (4, 7), (1, 6)];
+
+// Positions from random games on every size, including the starting position and the opening.
+fn positions(seed: u32) -> Vec<Game> {
+  let mut rng = XorShiftRng::from_seed([seed, 15, 15, 15]);
+  let mut positions = Vec::new();
+  for &(width, height) in &SIZES {
+    for _ in 0..3 {
+      let mut game = Game::new_black_sized(width, height).expect("valid board size");
+      loop {
+        positions.push(game);
+        let moves: Vec<Move> = game.legal_moves().collect();
+        if moves.is_empty() {
+          break;
+        }
+        game.next_turn(moves[rng.gen_range(0, moves.len())]).expect("legal move");
+      }
+    }
+  }
+  positions
+}
+
+#[test]
+fn inverses_undo_symmetries() {
+  for game in positions(1) {
+    let (width, height) = (game.papamu().width(), game.papamu().height());
+    for &symmetry in &SYMMETRIES {
+      let (new_width, new_height) = symmetry.transformed_size(width, height);
+      let transformed = game.transform(symmetry);
+      assert_eq!((transformed.papamu().width(), transformed.papamu().height()), (new_width, new_height));
+      assert_eq!(transformed.transform(symmetry.inverse()), game, "{:?}\n{}", symmetry, game.papamu());
+      assert_eq!(game.papamu().transform(symmetry).transform(symmetry.inverse()), *game.papamu());
+      for x in 0..width {
+        for y in 0..height {
+          let pos = Position::new(x, y).expect("position on the board");
+          let image = pos.transform(symmetry, width, height).expect("position on the board");
+          assert_eq!(image.transform(symmetry.inverse(), new_width, new_height), Some(pos));
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn colours_and_the_side_to_move() {
+  for game in positions(2) {
+    let (width, height) = (game.papamu().width(), game.papamu().height());
+    for &symmetry in &SYMMETRIES {
+      let transformed = game.transform(symmetry);
+      transformed.papamu().check_parity().unwrap_or_else(|err| panic!("{:?}: {}", symmetry, err));
+      let swapped = symmetry.swaps_colours(width, height);
+      assert_eq!(transformed.current_player() != game.current_player(), swapped);
+      assert_eq!(transformed.plies(), game.plies());
+      assert_eq!(transformed.is_opening(), game.is_opening());
+      assert_eq!(transformed.outcome().map(|outcome| outcome.winner),
+                 game.outcome().map(|outcome| symmetry.transform_occupancy(outcome.winner, width, height)));
+    }
+  }
+  // On a 6x4 board a mirror image swaps the colours one way but not the other.
+  let game = Game::new_black_sized(6, 4).expect("valid board size");
+  assert!(game.transform(Symmetry::FlipHorizontal).current_player().is_white());
+  assert!(game.transform(Symmetry::FlipVertical).current_player().is_white());
+  assert!(game.transform(Symmetry::Rotate180).current_player().is_black());
+}
+
+#[test]
+fn symmetries_commute_with_moves() {
+  for game in positions(3) {
+    let (width, height) = (game.papamu().width(), game.papamu().height());
+    for &symmetry in &SYMMETRIES {
+      let transformed = game.transform(symmetry);
+      let image = |mv: Move| mv.transform(symmetry, width, height).expect("move on the board");
+      let mut expected: Vec<Move> = game.legal_moves().map(&image).collect();
+      let mut actual: Vec<Move> = transformed.legal_moves().collect();
+      expected.sort_by_key(|mv| mv.to_string());
+      actual.sort_by_key(|mv| mv.to_string());
+      assert_eq!(actual, expected, "{:?}\n{}", symmetry, game.papamu());
+      for mv in game.legal_moves() {
+        let mut after = game;
+        after.next_turn(mv).expect("legal move");
+        let mut transformed_after = transformed;
+        transformed_after.next_turn(image(mv)).expect("legal move");
+        assert_eq!(transformed_after, after.transform(symmetry), "{:?} {}", symmetry, mv);
+      }
+    }
+  }
+}
+
+#[test]
+fn canonical_forms() {
+  for game in positions(4) {
+    let (canonical, symmetry) = game.canonical();
+    assert_eq!(game.transform(symmetry), canonical);
+    let (canonical_papamu, papamu_symmetry) = game.papamu().canonical();
+    assert_eq!(game.papamu().transform(papamu_symmetry), canonical_papamu);
+    for symmetry in game.papamu().symmetries() {
+      let image = game.transform(symmetry);
+      assert_eq!(image.canonical().0, canonical, "{:?}\n{}", symmetry, game.papamu());
+      assert_eq!(image.papamu().canonical().0, canonical_papamu);
+    }
+  }
+}
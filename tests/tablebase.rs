@@ -0,0 +1,159 @@
+// tablebase.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tablebases small enough to build in a debug build, checked against the solver.
+
+extern crate konane;
+
+use konane::{Game, Occupancy, RuleSet};
+use konane::errors::Error;
+use konane::solve::Solver;
+use konane::tablebase::Tablebase;
+
+const CELLS: usize = 12;
+
+fn build(rules: RuleSet) -> Tablebase { Tablebase::build(CELLS, rules).expect("few enough cells") }
+
+fn bytes_of(tablebase: &Tablebase) -> Vec<u8> {
+  let mut bytes = Vec::new();
+  tablebase.write_to(&mut bytes).expect("writing to memory");
+  bytes
+}
+
+// Checks the probe of `game` against the solver, and that the best move keeps the win.
+fn check_probe(tablebase: &Tablebase, solver: &mut Solver, game: &Game) {
+  let probe = game.probe(tablebase).expect("game covered by the tablebase");
+  let winner = solver.solve(game).expect("no budget").winner;
+  assert_eq!(probe.winner, winner, "\n{}", game);
+  match probe.best_move {
+    Some(mv) => {
+      let mut child = *game;
+      child.next_turn(mv).expect("legal move");
+      if winner == game.current_player() {
+        assert_eq!(solver.solve(&child).expect("no budget").winner, winner, "\n{}\n{}", game, mv);
+      }
+    },
+    None => assert!(game.is_over()),
+  }
+}
+
+#[test]
+fn round_trip() {
+  let tablebase = build(RuleSet::misere());
+  let bytes = bytes_of(&tablebase);
+  assert_eq!(&bytes[..4], b"KTB1");
+  assert_eq!(Tablebase::read_from(&bytes[..]).expect("valid tablebase"), tablebase);
+}
+
+#[test]
+fn bad_magic() {
+  let mut bytes = bytes_of(&build(RuleSet::standard()));
+  bytes[3] = b'2';
+  match Tablebase::read_from(&bytes[..]) {
+    Err(Error::BadTablebase { expected }) => assert_eq!(expected, "the bytes \"KTB1\""),
+    other => panic!("expected BadTablebase, got {:?}", other),
+  }
+}
+
+#[test]
+fn trailing_bytes() {
+  let mut bytes = bytes_of(&build(RuleSet::standard()));
+  bytes.push(0);
+  match Tablebase::read_from(&bytes[..]) {
+    Err(Error::BadTablebase { expected }) => assert_eq!(expected, "the end of the tablebase"),
+    other => panic!("expected BadTablebase, got {:?}", other),
+  }
+}
+
+#[test]
+fn truncated() {
+  let bytes = bytes_of(&build(RuleSet::standard()));
+  match Tablebase::read_from(&bytes[..bytes.len() - 1]) {
+    Err(Error::Io { .. }) => {},
+    other => panic!("expected Io, got {:?}", other),
+  }
+}
+
+#[test]
+fn agrees_with_the_solver_on_small_boards() {
+  for &rules in &[RuleSet::standard(), RuleSet::misere(), RuleSet::passing()] {
+    let tablebase = build(rules);
+    let mut solver = Solver::new();
+    for &(width, height) in &[(3, 4), (4, 3), (2, 6), (3, 3), (2, 5)] {
+      for seed in 1..6 {
+        let start = Game::new_black_sized(width, height).expect("valid board size").with_rules(rules);
+        let playout = start.seeded_playout([seed, 1, 2, 3], usize::max_value());
+        let mut game = start;
+        check_probe(&tablebase, &mut solver, &game);
+        for &mv in &playout.moves {
+          game.next_turn(mv).expect("legal move");
+          check_probe(&tablebase, &mut solver, &game);
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn one_live_region_on_a_large_board() {
+  let tablebase = build(RuleSet::standard());
+  let mut solver = Solver::new();
+  // The pieces on e2 and f2 make up the only region with moves left, on a board of its own from d1 to g3;
+  // the whole board is far too large for the tablebase.
+  let diagram = " 7 . . . . . . . x
+ 6 . . . . . . . .
+ 5 . . . . . . . x
+ 4 . . . . . . . .
+ 3 . . . . . . . .
+ 2 . . . . x o . .
+ 1 . . . . . . . .
+ 0 . . . . . . . .
+   A B C D E F G H";
+  for &(to_move, winner, best_move) in &[("black to move", Occupancy::Black, "e2-g2"),
+                                         ("white to move", Occupancy::White, "f2-d2")] {
+    let mut game = Game::from_diagram(&format!("{}\n{}", diagram, to_move)).expect("valid diagram");
+    assert!(!tablebase.covers(game.papamu()));
+    assert_eq!(game.papamu().regions().len(), 3);
+    check_probe(&tablebase, &mut solver, &game);
+    let probe = game.probe(&tablebase).expect("one live region");
+    assert_eq!(probe.winner, winner);
+    let mv = probe.best_move.expect("a move");
+    assert_eq!(mv.to_string(), best_move);
+    // Now no region has moves left, and the probe still answers.
+    game.next_turn(mv).expect("legal move");
+    assert_eq!(game.probe(&tablebase).map(|probe| probe.winner), Some(winner));
+  }
+}
+
+#[test]
+fn no_answer() {
+  let tablebase = build(RuleSet::standard());
+  let game = Game::from_diagram(" 7 . . . . . . o x
+ 6 . . . . . . . .
+ 5 . . . . . . . .
+ 4 . . . . . . . .
+ 3 . . . . . . . .
+ 2 . . . . . . . .
+ 1 . . . . . . . .
+ 0 x o . . . . . .
+   A B C D E F G H
+black to move")
+                 .expect("valid diagram");
+  assert_eq!(game.probe(&tablebase), None);
+  // Nor for a tablebase built under other rules, whatever the board.
+  let small = Game::new_black_sized(3, 4).expect("valid board size");
+  assert!(small.probe(&tablebase).is_some());
+  assert_eq!(small.with_rules(RuleSet::misere()).probe(&tablebase), None);
+}
@@ -0,0 +1,62 @@
+// zobrist.rs
+// Copyright 2016 Alexander Altman
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// `Game::zobrist` is kept up to date move by move; it must always equal the key computed from scratch.
+
+extern crate konane;
+
+use konane::{Game, Occupancy, Position, RuleSet};
+use konane::zobrist;
+
+fn full_key(game: &Game) -> u64 {
+  let papamu = game.papamu();
+  let mut key = zobrist::size_key(papamu.width(), papamu.height());
+  for x in 0..papamu.width() {
+    for y in 0..papamu.height() {
+      let pos = Position::new(x, y).expect("position on the board");
+      key ^= zobrist::piece_key(papamu[pos], pos);
+    }
+  }
+  assert_eq!(key, zobrist::papamu_key(papamu));
+  if game.current_player() == Occupancy::Black { key ^ zobrist::black_to_move_key() } else { key }
+}
+
+#[test]
+fn incremental_keys_under_every_ruleset() {
+  for &(name, rules) in RuleSet::presets() {
+    for &(width, height) in &[(6, 6), (5, 8), (1, 9)] {
+      for seed in 1..6 {
+        let start = Game::new_black_sized(width, height).expect("valid board size").with_rules(rules);
+        let mut game = start;
+        assert_eq!(game.zobrist(), full_key(&game));
+        for &mv in &start.seeded_playout([seed, width.into(), height.into(), 7], 200).moves {
+          game.next_turn(mv).expect("legal move");
+          assert_eq!(game.zobrist(), full_key(&game), "{} {}x{} after {}", name, width, height, mv);
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn keys_tell_the_side_to_move_and_the_size_apart() {
+  let black = Game::new_black_sized(6, 6).expect("valid board size");
+  let white = Game::new_white_sized(6, 6).expect("valid board size");
+  assert_eq!(black.zobrist() ^ white.zobrist(), zobrist::black_to_move_key());
+  let wide = Game::new_black_sized(6, 4).expect("valid board size");
+  let tall = Game::new_black_sized(4, 6).expect("valid board size");
+  assert!(wide.zobrist() != tall.zobrist());
+}